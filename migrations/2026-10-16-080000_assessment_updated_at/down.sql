@@ -0,0 +1,3 @@
+DROP INDEX dataset_assessments_updated_at_idx;
+
+ALTER TABLE dataset_assessments DROP COLUMN updated_at;
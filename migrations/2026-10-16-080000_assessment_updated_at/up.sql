@@ -0,0 +1,3 @@
+ALTER TABLE dataset_assessments ADD COLUMN updated_at TIMESTAMPTZ NOT NULL DEFAULT now();
+
+CREATE INDEX dataset_assessments_updated_at_idx ON dataset_assessments (updated_at);
@@ -0,0 +1,3 @@
+DROP TABLE aggregate_cache;
+
+ALTER TABLE dataset_assessments DROP COLUMN catalog_uri;
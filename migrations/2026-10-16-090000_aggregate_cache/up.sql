@@ -0,0 +1,10 @@
+ALTER TABLE dataset_assessments ADD COLUMN catalog_uri VARCHAR;
+
+CREATE TABLE aggregate_cache (
+    scope VARCHAR NOT NULL,
+    dimension_id VARCHAR NOT NULL,
+    score FLOAT8 NOT NULL,
+    max_score FLOAT8 NOT NULL,
+    computed_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (scope, dimension_id)
+);
@@ -0,0 +1,11 @@
+CREATE TABLE assessment_history (
+    id BIGSERIAL PRIMARY KEY,
+    assessment_id VARCHAR NOT NULL,
+    recorded_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    turtle_assessment VARCHAR NOT NULL,
+    jsonld_assessment VARCHAR NOT NULL,
+    json_score VARCHAR NOT NULL,
+    FOREIGN KEY (assessment_id) REFERENCES dataset_assessments (id) ON DELETE CASCADE
+);
+
+CREATE INDEX assessment_history_assessment_id_recorded_at_idx ON assessment_history (assessment_id, recorded_at);
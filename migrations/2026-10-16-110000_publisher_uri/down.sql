@@ -0,0 +1,3 @@
+DROP INDEX dataset_assessments_publisher_uri_idx;
+
+ALTER TABLE dataset_assessments DROP COLUMN publisher_uri;
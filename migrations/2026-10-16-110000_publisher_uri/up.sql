@@ -0,0 +1,3 @@
+ALTER TABLE dataset_assessments ADD COLUMN publisher_uri VARCHAR;
+
+CREATE INDEX dataset_assessments_publisher_uri_idx ON dataset_assessments (publisher_uri);
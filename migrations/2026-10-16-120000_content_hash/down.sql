@@ -0,0 +1 @@
+ALTER TABLE dataset_assessments DROP COLUMN content_hash;
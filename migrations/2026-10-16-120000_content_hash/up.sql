@@ -0,0 +1 @@
+ALTER TABLE dataset_assessments ADD COLUMN content_hash VARCHAR;
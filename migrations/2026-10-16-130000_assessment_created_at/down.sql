@@ -0,0 +1 @@
+ALTER TABLE dataset_assessments DROP COLUMN created_at;
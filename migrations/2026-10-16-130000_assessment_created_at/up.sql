@@ -0,0 +1,7 @@
+ALTER TABLE dataset_assessments ADD COLUMN created_at TIMESTAMPTZ NOT NULL DEFAULT now();
+
+-- Existing assessments were created no later than their first history snapshot.
+UPDATE dataset_assessments a SET created_at = LEAST(
+    a.updated_at,
+    COALESCE((SELECT MIN(h.recorded_at) FROM assessment_history h WHERE h.assessment_id = a.id), a.updated_at)
+);
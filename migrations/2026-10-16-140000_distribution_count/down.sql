@@ -0,0 +1 @@
+ALTER TABLE dataset_assessments DROP COLUMN distribution_count;
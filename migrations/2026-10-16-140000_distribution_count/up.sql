@@ -0,0 +1,4 @@
+ALTER TABLE dataset_assessments ADD COLUMN distribution_count INTEGER NOT NULL DEFAULT 0;
+
+UPDATE dataset_assessments SET distribution_count = json_array_length(json_score::json -> 'distributions')
+WHERE json_typeof(json_score::json -> 'distributions') = 'array';
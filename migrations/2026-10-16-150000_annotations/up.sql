@@ -0,0 +1,7 @@
+CREATE TABLE annotations (
+    assessment_id VARCHAR NOT NULL,
+    key VARCHAR NOT NULL,
+    value VARCHAR NOT NULL,
+    PRIMARY KEY (assessment_id, key),
+    FOREIGN KEY (assessment_id) REFERENCES dataset_assessments (id) ON DELETE CASCADE
+);
@@ -0,0 +1,7 @@
+UPDATE dataset_assessments a
+SET turtle_assessment = g.turtle_assessment, jsonld_assessment = g.jsonld_assessment
+FROM assessment_graphs g
+WHERE g.id = a.id AND a.turtle_assessment IS NULL;
+ALTER TABLE dataset_assessments ALTER COLUMN turtle_assessment SET NOT NULL;
+ALTER TABLE dataset_assessments ALTER COLUMN jsonld_assessment SET NOT NULL;
+DROP TABLE assessment_graphs;
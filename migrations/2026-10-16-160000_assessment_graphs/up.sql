@@ -0,0 +1,9 @@
+CREATE TABLE assessment_graphs (
+    id VARCHAR NOT NULL PRIMARY KEY,
+    turtle_assessment VARCHAR NOT NULL,
+    jsonld_assessment VARCHAR NOT NULL,
+    FOREIGN KEY (id) REFERENCES dataset_assessments (id) ON DELETE CASCADE
+);
+-- Null graphs are stored in assessment_graphs.
+ALTER TABLE dataset_assessments ALTER COLUMN turtle_assessment DROP NOT NULL;
+ALTER TABLE dataset_assessments ALTER COLUMN jsonld_assessment DROP NOT NULL;
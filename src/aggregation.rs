@@ -0,0 +1,615 @@
+use std::{
+    collections::{btree_map::Entry, BTreeMap, HashMap, HashSet},
+    str::FromStr,
+};
+
+use crate::{
+    db_models::DimensionSum,
+    error::Error,
+    graph,
+    models::{
+        DatasetDimensionScore, DatasetScore, Dimension, DimensionAggregate, DimensionStats,
+        PercentileRank, ScoreHistogram,
+    },
+};
+
+/// Share of `max_score` reached by `score` as a percentage between 0 and 100,
+/// rounded to `decimals` decimals. A `max_score` of zero yields 0.
+pub fn score_percent(score: f64, max_score: f64, decimals: u32) -> f64 {
+    if max_score <= 0.0 {
+        return 0.0;
+    }
+
+    let factor = 10f64.powi(decimals as i32);
+    ((score / max_score * 100.0).clamp(0.0, 100.0) * factor).round() / factor
+}
+
+/// Share of `max_score` reached by `score`, between 0 and 1. A `max_score` of
+/// zero yields 0.
+pub fn ratio(score: i32, max_score: i32) -> f64 {
+    if max_score <= 0 {
+        return 0.0;
+    }
+
+    (score as f64 / max_score as f64).clamp(0.0, 1.0)
+}
+
+/// How the overall ratio of a dataset or distribution is derived from its
+/// dimensions.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OverallStrategy {
+    /// Summed dimension scores over summed max scores. The default.
+    Sum,
+    /// Mean of the dimension ratios, weighing every dimension the same
+    /// regardless of its max score.
+    Weighted,
+    /// Ratio of the weakest dimension.
+    Min,
+}
+
+impl FromStr for OverallStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sum" => Ok(OverallStrategy::Sum),
+            "weighted" => Ok(OverallStrategy::Weighted),
+            "min" => Ok(OverallStrategy::Min),
+            _ => Err(format!("expected sum, weighted or min, got '{s}'")),
+        }
+    }
+}
+
+/// How the scores of a dimension are combined across datasets.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Aggregation {
+    /// Average score and max score. The default.
+    #[default]
+    Mean,
+    /// Median score and max score, which a few very poor datasets do not skew.
+    Median,
+    /// Average score and max score, with a ratio that weighs every dataset by
+    /// its max score so more complete assessments count more.
+    Weighted,
+}
+
+impl FromStr for Aggregation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mean" => Ok(Aggregation::Mean),
+            "median" => Ok(Aggregation::Median),
+            "weighted" => Ok(Aggregation::Weighted),
+            _ => Err(format!("expected mean, median or weighted, got '{s}'")),
+        }
+    }
+}
+
+/// How much each dataset counts in a mean over datasets.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum WeightBy {
+    /// Every dataset counts the same. The default.
+    #[default]
+    Datasets,
+    /// Datasets count once per distribution, and those without distributions
+    /// once.
+    Distributions,
+}
+
+impl FromStr for WeightBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "datasets" => Ok(WeightBy::Datasets),
+            "distributions" => Ok(WeightBy::Distributions),
+            _ => Err(format!("expected datasets or distributions, got '{s}'")),
+        }
+    }
+}
+
+/// Overall ratio, between 0 and 1, of the given dimensions. No dimensions
+/// yield 0.
+pub fn overall(dimensions: &[Dimension], strategy: OverallStrategy) -> f64 {
+    if dimensions.is_empty() {
+        return 0.0;
+    }
+
+    let ratios = dimensions.iter().map(|d| ratio(d.score, d.max_score));
+    match strategy {
+        OverallStrategy::Sum => ratio(
+            dimensions.iter().map(|d| d.score).sum(),
+            dimensions.iter().map(|d| d.max_score).sum(),
+        ),
+        OverallStrategy::Weighted => ratios.sum::<f64>() / dimensions.len() as f64,
+        OverallStrategy::Min => ratios.fold(1.0, f64::min),
+    }
+}
+
+/// Sets the ratio of every dataset and distribution dimension from its raw
+/// score, and their overall ratios by `strategy`, so all are serialized
+/// together.
+pub fn fill_ratios(score: &mut DatasetScore, strategy: OverallStrategy) {
+    let scores = std::iter::once(score.dataset.as_mut()).chain(score.distributions.iter_mut());
+    for score in scores {
+        for dimension in score.dimensions.iter_mut() {
+            dimension.ratio = ratio(dimension.score, dimension.max_score);
+        }
+        score.ratio = overall(&score.dimensions, strategy);
+    }
+}
+
+/// Uris of the datasets reaching the minimum ratio of every dimension in
+/// `thresholds`, as the intersection of the datasets passing each one. A
+/// dataset not scored in a thresholded dimension does not pass it.
+pub fn meeting_thresholds(
+    scores: &HashMap<String, DatasetScore>,
+    thresholds: &HashMap<String, f64>,
+) -> HashSet<String> {
+    thresholds
+        .iter()
+        .map(|(id, min_ratio)| {
+            scores
+                .iter()
+                .filter(|(_, score)| {
+                    score.dataset.dimensions.iter().any(|dimension| {
+                        dimension.id == *id
+                            && ratio(dimension.score, dimension.max_score) >= *min_ratio
+                    })
+                })
+                .map(|(dataset_uri, _)| dataset_uri.clone())
+                .collect::<HashSet<String>>()
+        })
+        .reduce(|passing, next| &passing & &next)
+        .unwrap_or_else(|| scores.keys().cloned().collect())
+}
+
+/// Transposes dataset scores into the scores of every dimension in
+/// `dimension_ids`, or of all when it is empty, each ordered by dataset uri.
+pub fn pivot_by_dimension(
+    scores: &HashMap<String, DatasetScore>,
+    dimension_ids: &[String],
+) -> HashMap<String, Vec<DatasetDimensionScore>> {
+    let mut dimensions: HashMap<String, Vec<DatasetDimensionScore>> = HashMap::new();
+    for (dataset_uri, score) in scores {
+        for dimension in &score.dataset.dimensions {
+            if !(dimension_ids.is_empty() || dimension_ids.contains(&dimension.id)) {
+                continue;
+            }
+            dimensions
+                .entry(dimension.id.clone())
+                .or_default()
+                .push(DatasetDimensionScore {
+                    dataset_uri: dataset_uri.clone(),
+                    score: dimension.score,
+                    max_score: dimension.max_score,
+                });
+        }
+    }
+    for datasets in dimensions.values_mut() {
+        datasets.sort_by(|a, b| a.dataset_uri.cmp(&b.dataset_uri));
+    }
+    dimensions
+}
+
+/// Average score and max score per dimension, with the scores read from the
+/// Turtle graphs instead of the `dimensions` table. The graphs carry no max
+/// scores, so those come from the JSON scores stored with the same assessment.
+pub fn graph_aggregates(
+    graphs: &HashMap<String, String>,
+    scores: &HashMap<String, DatasetScore>,
+) -> Result<Vec<DimensionAggregate>, Error> {
+    let mut sums: BTreeMap<String, (f64, f64, u32)> = BTreeMap::new();
+    for (dataset_uri, turtle) in graphs {
+        for (id, score) in graph::dimension_scores(turtle, dataset_uri)? {
+            let max_score = scores
+                .get(dataset_uri)
+                .and_then(|s| s.dataset.dimensions.iter().find(|d| d.id == id))
+                .map_or(0, |d| d.max_score);
+            let sum = sums.entry(id).or_default();
+            sum.0 += score as f64;
+            sum.1 += max_score as f64;
+            sum.2 += 1;
+        }
+    }
+
+    Ok(sums
+        .into_iter()
+        .map(|(id, (score, max_score, count))| {
+            DimensionAggregate::new(id, score / count as f64, max_score / count as f64)
+        })
+        .collect())
+}
+
+/// Two-sided 95% quantile of the standard normal distribution.
+const Z_95: f64 = 1.959_963_984_540_054;
+
+/// Mean dataset score per dimension with the sample standard deviation and a
+/// 95% confidence interval of the mean by normal approximation. Both are left
+/// out for dimensions scored by fewer than two datasets.
+pub fn dimension_stats(
+    scores: &HashMap<String, DatasetScore>,
+    dimension_ids: &[String],
+) -> Vec<DimensionStats> {
+    let mut samples: BTreeMap<String, (Vec<f64>, f64)> = BTreeMap::new();
+    for score in scores.values() {
+        for dimension in &score.dataset.dimensions {
+            if !(dimension_ids.is_empty() || dimension_ids.contains(&dimension.id)) {
+                continue;
+            }
+            let sample = samples.entry(dimension.id.clone()).or_default();
+            sample.0.push(dimension.score as f64);
+            sample.1 += dimension.max_score as f64;
+        }
+    }
+
+    samples
+        .into_iter()
+        .map(|(id, (values, max_score))| {
+            let n = values.len() as f64;
+            let mean = values.iter().sum::<f64>() / n;
+            let mut stats = DimensionStats::new(id, values.len() as i64, mean, max_score / n);
+            if values.len() > 1 {
+                let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0);
+                let stddev = variance.sqrt();
+                let margin = Z_95 * stddev / n.sqrt();
+                stats.stddev = Some(stddev);
+                stats.ci_lower = Some(mean - margin);
+                stats.ci_upper = Some(mean + margin);
+            }
+            stats
+        })
+        .collect()
+}
+
+/// Per-dimension sums and extremes over batches of datasets, combining to the
+/// aggregates of all of them once every batch is added.
+#[derive(Default)]
+pub struct AggregateSums(BTreeMap<String, DimensionSum>);
+
+impl AggregateSums {
+    pub fn add(&mut self, sums: Vec<DimensionSum>) {
+        for sum in sums {
+            match self.0.entry(sum.id.clone()) {
+                Entry::Vacant(entry) => {
+                    entry.insert(sum);
+                }
+                Entry::Occupied(mut entry) => {
+                    let total = entry.get_mut();
+                    total.score += sum.score;
+                    total.max_score += sum.max_score;
+                    total.count += sum.count;
+                    total.ratio += sum.ratio;
+                    total.min_observed_score = total.min_observed_score.min(sum.min_observed_score);
+                    total.max_observed_score = total.max_observed_score.max(sum.max_observed_score);
+                    total.min_ratio = total.min_ratio.min(sum.min_ratio);
+                    total.max_ratio = total.max_ratio.max(sum.max_ratio);
+                }
+            }
+        }
+    }
+
+    /// Average score, max score and ratio per dimension, with the lowest and
+    /// highest score and ratio, ordered by dimension id.
+    pub fn aggregates(&self) -> Vec<DimensionAggregate> {
+        self.0
+            .values()
+            .map(|sum| {
+                let count = sum.count as f64;
+                DimensionAggregate {
+                    id: sum.id.clone(),
+                    score: sum.score as f64 / count,
+                    max_score: sum.max_score as f64 / count,
+                    ratio: Some(sum.ratio / count),
+                    min_observed_score: Some(sum.min_observed_score),
+                    max_observed_score: Some(sum.max_observed_score),
+                    min_ratio: Some(sum.min_ratio),
+                    max_ratio: Some(sum.max_ratio),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Percentile rank of the `dimension` ratio of `dataset_uri` among itself and
+/// the peers scored in that dimension: the percentage of them with a lower
+/// ratio, counting ties (the dataset included) as half. `None` when the
+/// dataset is not scored in the dimension.
+pub fn percentile_rank(
+    scores: &HashMap<String, DatasetScore>,
+    dataset_uri: &str,
+    peer_uris: &[String],
+    dimension: &str,
+) -> Option<PercentileRank> {
+    let dimension_ratio = |uri: &str| {
+        scores.get(uri).and_then(|score| {
+            score
+                .dataset
+                .dimensions
+                .iter()
+                .find(|d| d.id == dimension)
+                .map(|d| ratio(d.score, d.max_score))
+        })
+    };
+    let own = dimension_ratio(dataset_uri)?;
+
+    let mut ranked: HashSet<&str> = peer_uris.iter().map(String::as_str).collect();
+    ranked.insert(dataset_uri);
+    let ratios: Vec<f64> = ranked.into_iter().filter_map(dimension_ratio).collect();
+    let below = ratios.iter().filter(|r| **r < own).count() as f64;
+    let tied = ratios.iter().filter(|r| **r == own).count() as f64;
+
+    Some(PercentileRank::new(
+        dataset_uri.to_string(),
+        dimension.to_string(),
+        own,
+        (below + tied / 2.0) / ratios.len() as f64 * 100.0,
+        ratios.len() as i64,
+    ))
+}
+
+/// Number of datasets whose overall ratio, by `strategy`, falls in each of
+/// `buckets` equally wide buckets between 0 and 1. Every bucket includes its
+/// lower edge, and the last one its upper edge as well.
+pub fn ratio_histogram(
+    scores: &HashMap<String, DatasetScore>,
+    buckets: usize,
+    strategy: OverallStrategy,
+) -> ScoreHistogram {
+    let bucket_edges: Vec<f64> = (0..=buckets).map(|i| i as f64 / buckets as f64).collect();
+    let mut counts = vec![0; buckets];
+    for score in scores.values() {
+        let ratio = overall(&score.dataset.dimensions, strategy);
+        // Compared with the edges themselves, so a ratio equal to an edge is
+        // not moved down a bucket by rounding.
+        let bucket = bucket_edges[1..buckets]
+            .iter()
+            .filter(|edge| ratio >= **edge)
+            .count();
+        counts[bucket] += 1;
+    }
+    ScoreHistogram::new(bucket_edges, counts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Dimension, Score};
+
+    #[test]
+    fn test_score_percent_rounding() {
+        assert_eq!(score_percent(2.0, 3.0, 0), 67.0);
+        assert_eq!(score_percent(2.0, 3.0, 1), 66.7);
+        assert_eq!(score_percent(2.0, 3.0, 2), 66.67);
+        assert_eq!(score_percent(70.0, 100.0, 0), 70.0);
+    }
+
+    #[test]
+    fn test_meeting_thresholds() {
+        let score = |dimensions: Vec<(&str, i32)>| DatasetScore {
+            dataset: Box::new(Score {
+                dimensions: dimensions
+                    .into_iter()
+                    .map(|(id, score)| Dimension::new(id.to_string(), vec![], score, 10))
+                    .collect(),
+                ..Default::default()
+            }),
+            distributions: vec![],
+        };
+        let scores = HashMap::from([
+            ("https://a".to_string(), score(vec![("x", 9), ("y", 5)])),
+            ("https://b".to_string(), score(vec![("x", 9), ("y", 4)])),
+            ("https://c".to_string(), score(vec![("x", 7), ("y", 9)])),
+            ("https://d".to_string(), score(vec![("x", 10)])),
+        ]);
+
+        let thresholds = HashMap::from([("x".to_string(), 0.8), ("y".to_string(), 0.5)]);
+        assert_eq!(
+            meeting_thresholds(&scores, &thresholds),
+            HashSet::from(["https://a".to_string()])
+        );
+        assert_eq!(meeting_thresholds(&scores, &HashMap::new()).len(), 4);
+    }
+
+    #[test]
+    fn test_aggregate_sums() {
+        // Every dimension of these datasets has a max score of 10.
+        let sum = |id: &str, score, count, min: f64, max: f64| DimensionSum {
+            id: id.to_string(),
+            score,
+            max_score: 10 * count,
+            count,
+            ratio: score as f64 / 10.0,
+            min_observed_score: min,
+            max_observed_score: max,
+            min_ratio: min / 10.0,
+            max_ratio: max / 10.0,
+        };
+        let mut sums = AggregateSums::default();
+        sums.add(vec![sum("y", 10, 2, 4.0, 6.0), sum("x", 3, 1, 3.0, 3.0)]);
+        sums.add(vec![sum("x", 5, 1, 5.0, 5.0)]);
+        sums.add(vec![]);
+
+        let spread = |id: &str, score, ratio, min: f64, max: f64| DimensionAggregate {
+            ratio: Some(ratio),
+            min_observed_score: Some(min),
+            max_observed_score: Some(max),
+            min_ratio: Some(min / 10.0),
+            max_ratio: Some(max / 10.0),
+            ..DimensionAggregate::new(id.to_string(), score, 10.0)
+        };
+        assert_eq!(
+            sums.aggregates(),
+            vec![
+                spread("x", 4.0, 0.4, 3.0, 5.0),
+                spread("y", 5.0, 0.5, 4.0, 6.0),
+            ]
+        );
+        assert!(AggregateSums::default().aggregates().is_empty());
+    }
+
+    #[test]
+    fn test_ratio_histogram() {
+        let score = |x: i32| DatasetScore {
+            dataset: Box::new(Score {
+                dimensions: vec![Dimension::new("x".to_string(), vec![], x, 100)],
+                ..Default::default()
+            }),
+            distributions: vec![],
+        };
+        let scores: HashMap<String, DatasetScore> = [0, 57, 100]
+            .into_iter()
+            .map(|x| (format!("https://{x}"), score(x)))
+            .collect();
+
+        // 0.57 * 100 rounds below 57, but 0.57 is still counted at its own edge.
+        let histogram = ratio_histogram(&scores, 100, OverallStrategy::Sum);
+        assert_eq!(histogram.bucket_edges.len(), 101);
+        assert_eq!(histogram.bucket_edges[57], 0.57);
+        assert_eq!(histogram.counts.len(), 100);
+        assert_eq!(histogram.counts[0], 1);
+        assert_eq!(histogram.counts[57], 1);
+        assert_eq!(histogram.counts[99], 1);
+        assert_eq!(histogram.counts.iter().sum::<i64>(), 3);
+
+        let histogram = ratio_histogram(&HashMap::new(), 1, OverallStrategy::Sum);
+        assert_eq!(histogram, ScoreHistogram::new(vec![0.0, 1.0], vec![0]));
+    }
+
+    #[test]
+    fn test_percentile_rank() {
+        let score = |x: i32| DatasetScore {
+            dataset: Box::new(Score {
+                dimensions: vec![Dimension::new("x".to_string(), vec![], x, 10)],
+                ..Default::default()
+            }),
+            distributions: vec![],
+        };
+        let scores = HashMap::from([
+            ("https://a".to_string(), score(2)),
+            ("https://b".to_string(), score(4)),
+            ("https://c".to_string(), score(6)),
+            ("https://d".to_string(), score(6)),
+            ("https://e".to_string(), score(9)),
+        ]);
+        let peers: Vec<String> = ["a", "b", "c", "d", "e", "unscored"]
+            .iter()
+            .map(|p| format!("https://{p}"))
+            .collect();
+
+        let rank = percentile_rank(&scores, "https://c", &peers, "x").unwrap();
+        assert_eq!(rank.ratio, 0.6);
+        assert_eq!(rank.ranked_count, 5);
+        assert_eq!(rank.percentile_rank, 60.0);
+
+        // A dataset outside the peer set is ranked among them.
+        let rank = percentile_rank(&scores, "https://e", &peers[..4], "x").unwrap();
+        assert_eq!(rank.ranked_count, 5);
+        assert_eq!(rank.percentile_rank, 90.0);
+
+        let rank = percentile_rank(&scores, "https://a", &[], "x").unwrap();
+        assert_eq!(rank.percentile_rank, 50.0);
+
+        assert_eq!(percentile_rank(&scores, "https://a", &peers, "y"), None);
+        assert_eq!(
+            percentile_rank(&scores, "https://unscored", &peers, "x"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_ratio() {
+        assert_eq!(ratio(70, 100), 0.7);
+        assert_eq!(ratio(0, 140), 0.0);
+        assert_eq!(ratio(10, 0), 0.0);
+        assert_eq!(ratio(150, 140), 1.0);
+    }
+
+    fn overall_dimensions() -> Vec<Dimension> {
+        vec![
+            Dimension::new("a".to_string(), vec![], 50, 100),
+            Dimension::new("b".to_string(), vec![], 20, 20),
+            Dimension::new("c".to_string(), vec![], 0, 80),
+        ]
+    }
+
+    #[test]
+    fn test_overall_sum() {
+        assert_eq!(overall(&overall_dimensions(), OverallStrategy::Sum), 0.35);
+    }
+
+    #[test]
+    fn test_overall_weighted() {
+        assert_eq!(
+            overall(&overall_dimensions(), OverallStrategy::Weighted),
+            0.5
+        );
+    }
+
+    #[test]
+    fn test_overall_min() {
+        assert_eq!(overall(&overall_dimensions(), OverallStrategy::Min), 0.0);
+        assert_eq!(
+            overall(&overall_dimensions()[..2], OverallStrategy::Min),
+            0.5
+        );
+    }
+
+    #[test]
+    fn test_overall_without_dimensions() {
+        for strategy in [
+            OverallStrategy::Sum,
+            OverallStrategy::Weighted,
+            OverallStrategy::Min,
+        ] {
+            assert_eq!(overall(&[], strategy), 0.0);
+        }
+    }
+
+    #[test]
+    fn test_score_percent_range() {
+        assert_eq!(score_percent(0.0, 140.0, 0), 0.0);
+        assert_eq!(score_percent(140.0, 140.0, 0), 100.0);
+        assert_eq!(score_percent(150.0, 140.0, 2), 100.0);
+        assert_eq!(score_percent(10.0, 0.0, 2), 0.0);
+    }
+
+    #[test]
+    fn test_dimension_stats() {
+        let dataset = |score: i32| {
+            DatasetScore::new(
+                Score::new(
+                    String::new(),
+                    vec![Dimension::new("a".to_string(), vec![], score, 100)],
+                    score,
+                    100,
+                ),
+                vec![],
+            )
+        };
+        let scores = HashMap::from([
+            ("https://dataset.a".to_string(), dataset(10)),
+            ("https://dataset.b".to_string(), dataset(20)),
+            ("https://dataset.c".to_string(), dataset(30)),
+        ]);
+
+        let stats = dimension_stats(&scores, &[]);
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].count, 3);
+        assert_eq!(stats[0].mean, 20.0);
+        assert_eq!(stats[0].max_score, 100.0);
+        assert_eq!(stats[0].stddev, Some(10.0));
+        assert!((stats[0].ci_lower.unwrap() - 8.684).abs() < 1e-3);
+        assert!((stats[0].ci_upper.unwrap() - 31.316).abs() < 1e-3);
+
+        let single = dimension_stats(
+            &HashMap::from([("https://dataset.a".to_string(), dataset(10))]),
+            &[],
+        );
+        assert_eq!(single[0].stddev, None);
+        assert_eq!(single[0].ci_lower, None);
+
+        assert!(dimension_stats(&scores, &["b".to_string()]).is_empty());
+    }
+}
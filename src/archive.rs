@@ -0,0 +1,116 @@
+//! Tar archives written on a blocking thread and streamed to the client as
+//! they are produced.
+
+use std::{
+    fmt::Display,
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use actix_web::{
+    body::{BodySize, MessageBody},
+    web::Bytes,
+};
+use sha2::{Digest, Sha256};
+use tokio::sync::mpsc;
+
+/// Entries buffered ahead of a slow client before the writer blocks, which
+/// bounds the memory used by an export.
+const BUFFERED_ENTRIES: usize = 16;
+
+/// Two zero blocks marking the end of a tar archive.
+const END_OF_ARCHIVE: [u8; 1024] = [0; 1024];
+
+/// Writing half of a streamed archive.
+pub struct ArchiveWriter(mpsc::Sender<io::Result<Bytes>>);
+
+/// Response body yielding the archive as the writer appends to it.
+pub struct ArchiveBody(mpsc::Receiver<io::Result<Bytes>>);
+
+pub fn channel() -> (ArchiveWriter, ArchiveBody) {
+    let (sender, receiver) = mpsc::channel(BUFFERED_ENTRIES);
+    (ArchiveWriter(sender), ArchiveBody(receiver))
+}
+
+/// Name stem of the entries of a dataset: the hex SHA-256 of its uri.
+pub fn uri_hash(uri: &str) -> String {
+    hex::encode(Sha256::digest(uri.as_bytes()))
+}
+
+impl ArchiveWriter {
+    /// Appends a file, blocking while the client is behind. Returns `false`
+    /// once the client has gone away.
+    pub fn append(&self, path: &str, data: &[u8]) -> bool {
+        self.0
+            .blocking_send(entry(path, data).map(Bytes::from))
+            .is_ok()
+    }
+
+    /// Ends the archive, or aborts the response if writing it failed.
+    pub fn finish<E: Display>(self, result: Result<(), E>) {
+        let _ = self.0.blocking_send(match result {
+            Ok(()) => Ok(Bytes::from_static(&END_OF_ARCHIVE)),
+            Err(e) => Err(io::Error::other(e.to_string())),
+        });
+    }
+}
+
+impl MessageBody for ArchiveBody {
+    type Error = io::Error;
+
+    fn size(&self) -> BodySize {
+        BodySize::Stream
+    }
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<io::Result<Bytes>>> {
+        self.0.poll_recv(cx)
+    }
+}
+
+/// A tar header for a regular file followed by its zero-padded content.
+fn entry(path: &str, data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut header = tar::Header::new_ustar();
+    header.set_path(path)?;
+    header.set_size(data.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+
+    let mut chunk = header.as_bytes().to_vec();
+    chunk.extend_from_slice(data);
+    chunk.resize(chunk.len().next_multiple_of(512), 0);
+    Ok(chunk)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::*;
+
+    #[test]
+    fn test_entry() {
+        let mut archive = entry("a.json", b"{}").unwrap();
+        archive.extend_from_slice(&entry("a.ttl", b"").unwrap());
+        archive.extend_from_slice(&END_OF_ARCHIVE);
+        assert_eq!(archive.len() % 512, 0);
+
+        let mut files = Vec::new();
+        for file in tar::Archive::new(archive.as_slice()).entries().unwrap() {
+            let mut file = file.unwrap();
+            let mut content = String::new();
+            file.read_to_string(&mut content).unwrap();
+            files.push((file.path().unwrap().display().to_string(), content));
+        }
+        assert_eq!(
+            files,
+            vec![
+                ("a.json".to_string(), "{}".to_string()),
+                ("a.ttl".to_string(), "".to_string())
+            ]
+        );
+    }
+}
@@ -0,0 +1,95 @@
+//! CSV written on a blocking thread and streamed to the client as it is
+//! produced.
+
+use std::{
+    fmt::Display,
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use actix_web::{
+    body::{BodySize, MessageBody},
+    web::Bytes,
+};
+use tokio::sync::mpsc;
+
+/// Rows buffered ahead of a slow client before the writer blocks, which bounds
+/// the memory used by an export.
+const BUFFERED_ROWS: usize = 64;
+
+/// Writing half of a streamed CSV document.
+pub struct CsvWriter(mpsc::Sender<io::Result<Bytes>>);
+
+/// Response body yielding the rows as the writer sends them.
+pub struct CsvBody(mpsc::Receiver<io::Result<Bytes>>);
+
+pub fn channel() -> (CsvWriter, CsvBody) {
+    let (sender, receiver) = mpsc::channel(BUFFERED_ROWS);
+    (CsvWriter(sender), CsvBody(receiver))
+}
+
+impl CsvWriter {
+    /// Appends a row, blocking while the client is behind. Returns `false`
+    /// once the client has gone away.
+    pub fn row<S: AsRef<str>>(&self, fields: &[S]) -> bool {
+        self.0
+            .blocking_send(Ok(Bytes::from(record(fields))))
+            .is_ok()
+    }
+
+    /// Ends the document, or aborts the response if writing it failed.
+    pub fn finish<E: Display>(self, result: Result<(), E>) {
+        if let Err(e) = result {
+            let _ = self.0.blocking_send(Err(io::Error::other(e.to_string())));
+        }
+    }
+}
+
+impl MessageBody for CsvBody {
+    type Error = io::Error;
+
+    fn size(&self) -> BodySize {
+        BodySize::Stream
+    }
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<io::Result<Bytes>>> {
+        self.0.poll_recv(cx)
+    }
+}
+
+/// The fields as a CSV record ending in CRLF, quoting those that contain a
+/// comma, a quote or a line break as RFC 4180 describes.
+pub fn record<S: AsRef<str>>(fields: &[S]) -> String {
+    let mut record = fields
+        .iter()
+        .map(|field| {
+            let field = field.as_ref();
+            if field.contains([',', '"', '\r', '\n']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    record.push_str("\r\n");
+    record
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record() {
+        assert_eq!(record(&["a", "", "1"]), "a,,1\r\n");
+        assert_eq!(
+            record(&["https://a,b", "say \"hi\"", "two\nlines"]),
+            "\"https://a,b\",\"say \"\"hi\"\"\",\"two\nlines\"\r\n"
+        );
+    }
+}
@@ -1,18 +1,48 @@
 use super::schema::*;
-use diesel::sql_types::Double;
+use diesel::sql_types::{Array, BigInt, Bool, Double, Nullable, Text};
 
 #[derive(Insertable, Queryable, AsChangeset)]
-#[table_name = "dataset_assessments"]
+#[diesel(table_name = dataset_assessments, treat_none_as_null = true)]
 pub struct DatasetAssessment {
     pub id: String,
     pub dataset_uri: String,
-    pub turtle_assessment: String,
-    pub jsonld_assessment: String,
+    /// `None` when the graph is stored in `assessment_graphs`.
+    pub turtle_assessment: Option<String>,
+    pub jsonld_assessment: Option<String>,
     pub json_score: String,
+    pub catalog_uri: Option<String>,
+    pub publisher_uri: Option<String>,
+    pub content_hash: Option<String>,
+    pub distribution_count: i32,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = assessment_history)]
+pub struct AssessmentSnapshot<'a> {
+    pub assessment_id: &'a str,
+    pub turtle_assessment: &'a str,
+    pub jsonld_assessment: &'a str,
+    pub json_score: &'a str,
+}
+
+#[derive(Insertable, AsChangeset)]
+#[diesel(table_name = assessment_graphs)]
+pub struct AssessmentGraphs<'a> {
+    pub id: &'a str,
+    pub turtle_assessment: &'a str,
+    pub jsonld_assessment: &'a str,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = annotations)]
+pub struct Annotation<'a> {
+    pub assessment_id: &'a str,
+    pub key: &'a str,
+    pub value: &'a str,
 }
 
 #[derive(Insertable, Queryable, AsChangeset)]
-#[table_name = "dimensions"]
+#[diesel(table_name = dimensions)]
 pub struct Dimension {
     pub dataset_uri: String,
     pub id: String,
@@ -21,11 +51,109 @@ pub struct Dimension {
 }
 
 #[derive(QueryableByName)]
-#[table_name = "dimensions"]
+#[diesel(table_name = dimensions)]
 pub struct DimensionAggregate {
     pub id: String,
-    #[sql_type = "Double"]
+    #[diesel(sql_type = Double)]
     pub score: f64,
-    #[sql_type = "Double"]
+    #[diesel(sql_type = Double)]
     pub max_score: f64,
 }
+
+#[derive(QueryableByName)]
+#[diesel(table_name = dimensions)]
+pub struct DimensionSpread {
+    pub id: String,
+    #[diesel(sql_type = Double)]
+    pub score: f64,
+    #[diesel(sql_type = Double)]
+    pub max_score: f64,
+    #[diesel(sql_type = Double)]
+    pub ratio: f64,
+    #[diesel(sql_type = Double)]
+    pub min_observed_score: f64,
+    #[diesel(sql_type = Double)]
+    pub max_observed_score: f64,
+    #[diesel(sql_type = Double)]
+    pub min_ratio: f64,
+    #[diesel(sql_type = Double)]
+    pub max_ratio: f64,
+}
+
+#[derive(QueryableByName)]
+#[diesel(table_name = dimensions)]
+pub struct DimensionPercentiles {
+    pub id: String,
+    #[diesel(sql_type = Array<Double>)]
+    pub scores: Vec<f64>,
+}
+
+#[derive(QueryableByName)]
+#[diesel(table_name = dimensions)]
+pub struct DimensionSum {
+    pub id: String,
+    #[diesel(sql_type = BigInt)]
+    pub score: i64,
+    #[diesel(sql_type = BigInt)]
+    pub max_score: i64,
+    #[diesel(sql_type = BigInt)]
+    pub count: i64,
+    #[diesel(sql_type = Double)]
+    pub ratio: f64,
+    #[diesel(sql_type = Double)]
+    pub min_observed_score: f64,
+    #[diesel(sql_type = Double)]
+    pub max_observed_score: f64,
+    #[diesel(sql_type = Double)]
+    pub min_ratio: f64,
+    #[diesel(sql_type = Double)]
+    pub max_ratio: f64,
+}
+
+#[derive(QueryableByName)]
+#[diesel(table_name = dimensions)]
+pub struct DimensionOverview {
+    pub id: String,
+    #[diesel(sql_type = Double)]
+    pub average_ratio: f64,
+    #[diesel(sql_type = BigInt)]
+    pub dataset_count: i64,
+}
+
+#[derive(QueryableByName)]
+pub struct CatalogCount {
+    #[diesel(sql_type = Text)]
+    pub catalog_uri: String,
+    #[diesel(sql_type = BigInt)]
+    pub assessment_count: i64,
+    #[diesel(sql_type = Double)]
+    pub avg_overall_ratio: f64,
+}
+
+#[derive(QueryableByName)]
+#[diesel(table_name = dimensions)]
+pub struct DimensionComparison {
+    pub id: String,
+    #[diesel(sql_type = Nullable<Double>)]
+    pub a: Option<f64>,
+    #[diesel(sql_type = Nullable<Double>)]
+    pub b: Option<f64>,
+}
+
+#[derive(QueryableByName)]
+pub struct ReplicationStatus {
+    #[diesel(sql_type = Bool)]
+    pub in_recovery: bool,
+    #[diesel(sql_type = Nullable<Double>)]
+    pub lag_seconds: Option<f64>,
+}
+
+#[derive(QueryableByName)]
+#[diesel(table_name = dimensions)]
+pub struct DimensionVsAverage {
+    pub id: String,
+    #[diesel(sql_type = Double)]
+    pub ratio: f64,
+    #[diesel(sql_type = Double)]
+    pub average_ratio: f64,
+}
@@ -0,0 +1,106 @@
+use crate::models::{DatasetScore, DimensionDiff};
+
+/// Dataset dimensions whose score differs between `previous` and `current`,
+/// ordered as in `current` followed by dimensions that were removed. Without a
+/// previous score every dimension is reported as new.
+pub fn dimension_diff(
+    previous: Option<&DatasetScore>,
+    current: &DatasetScore,
+) -> Vec<DimensionDiff> {
+    let previous_dimensions = previous
+        .map(|score| score.dataset.dimensions.as_slice())
+        .unwrap_or_default();
+    let previous_score = |id: &str| {
+        previous_dimensions
+            .iter()
+            .find(|dimension| dimension.id == id)
+            .map(|dimension| dimension.score)
+    };
+
+    let changed = current.dataset.dimensions.iter().filter_map(|dimension| {
+        let previous_score = previous_score(&dimension.id);
+        (previous_score != Some(dimension.score)).then(|| DimensionDiff {
+            id: dimension.id.clone(),
+            previous_score,
+            score: Some(dimension.score),
+        })
+    });
+    let removed = previous_dimensions
+        .iter()
+        .filter(|previous| {
+            !current
+                .dataset
+                .dimensions
+                .iter()
+                .any(|dimension| dimension.id == previous.id)
+        })
+        .map(|previous| DimensionDiff {
+            id: previous.id.clone(),
+            previous_score: Some(previous.score),
+            score: None,
+        });
+
+    changed.chain(removed).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Dimension, Score};
+
+    fn dataset_score(dimensions: &[(&str, i32)]) -> DatasetScore {
+        DatasetScore::new(
+            Score::new(
+                "https://dataset.foo".to_string(),
+                dimensions
+                    .iter()
+                    .map(|(id, score)| Dimension::new(id.to_string(), vec![], *score, 100))
+                    .collect(),
+                0,
+                0,
+            ),
+            vec![],
+        )
+    }
+
+    #[test]
+    fn test_dimension_diff() {
+        let previous = dataset_score(&[("a", 10), ("b", 20), ("c", 30)]);
+        let current = dataset_score(&[("a", 10), ("b", 25), ("d", 40)]);
+
+        assert_eq!(
+            dimension_diff(Some(&previous), &current),
+            vec![
+                DimensionDiff {
+                    id: "b".to_string(),
+                    previous_score: Some(20),
+                    score: Some(25),
+                },
+                DimensionDiff {
+                    id: "d".to_string(),
+                    previous_score: None,
+                    score: Some(40),
+                },
+                DimensionDiff {
+                    id: "c".to_string(),
+                    previous_score: Some(30),
+                    score: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dimension_diff_without_previous() {
+        let current = dataset_score(&[("a", 10)]);
+
+        assert_eq!(
+            dimension_diff(None, &current),
+            vec![DimensionDiff {
+                id: "a".to_string(),
+                previous_score: None,
+                score: Some(10),
+            }]
+        );
+    }
+}
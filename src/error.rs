@@ -1,20 +1,51 @@
-use actix_web::{HttpResponse, ResponseError};
+use actix_web::{http::header, HttpResponse, ResponseError};
 use serde::Serialize;
 use thiserror::Error;
 use uuid::Uuid;
 
-use crate::database;
+use crate::{database, models::FieldError};
 
+#[allow(clippy::enum_variant_names)]
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("dataset with FDK ID '{0}' does not exist")]
     NotFound(Uuid),
+    #[error("dataset '{0}' has no stored score")]
+    DatasetNotScored(String),
     #[error("invalid FDK ID: '{0}'")]
     InvalidID(String),
+    #[error("invalid request")]
+    InvalidRequest(Vec<FieldError>),
+    #[error("invalid graph: {0}")]
+    InvalidGraph(String),
+    #[error("dimension ids not referenced in the assessment graph")]
+    DimensionMismatch(Vec<FieldError>),
+    #[error("duplicate dimension ids")]
+    DuplicateDimensions(Vec<FieldError>),
+    #[error("scores out of range")]
+    ScoreOutOfRange(Vec<FieldError>),
+    #[error("dimension scores exceeding their max score")]
+    InvalidScore(Vec<FieldError>),
+    #[error("dimension scores differing from the assessment graph")]
+    ScoreMismatch(Vec<FieldError>),
+    #[error("percentiles out of range")]
+    PercentileOutOfRange(Vec<FieldError>),
+    #[error("dataset already assessed under another FDK ID")]
+    DatasetConflict(Vec<FieldError>),
+    #[error("none of the accepted media types are available, expected one of {}", .0.join(", "))]
+    NotAcceptable(Vec<&'static str>),
     #[error(transparent)]
     InvalidUri(#[from] http::uri::InvalidUri),
+    #[error("{0} exceeds the graph size limit of {1} bytes")]
+    GraphTooLarge(&'static str, usize),
+    #[error("parsing the graph took longer than {0} ms")]
+    GraphParseTimeout(u64),
     #[error("Unauthorized: {0}")]
     Unauthorized(String),
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+    #[error("writes are frozen for maintenance, retry in {0} seconds")]
+    WriteFrozen(u64),
     #[error(transparent)]
     DatabaseError(#[from] database::DatabaseError),
     #[error(transparent)]
@@ -27,18 +58,144 @@ pub enum Error {
 
 impl ResponseError for Error {
     fn error_response(&self) -> HttpResponse {
+        self.localized_response(Language::English)
+    }
+}
+
+/// Languages user-facing error messages are available in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Language {
+    English,
+    Norwegian,
+}
+
+impl Language {
+    /// Most preferred supported language of an `Accept-Language` header,
+    /// English when none is supported.
+    pub fn negotiate(accept: &header::AcceptLanguage) -> Self {
+        accept
+            .ranked()
+            .iter()
+            .find_map(|preference| match preference {
+                header::Preference::Specific(tag) => match tag.primary_language() {
+                    "nb" | "nn" | "no" => Some(Language::Norwegian),
+                    "en" => Some(Language::English),
+                    _ => None,
+                },
+                header::Preference::Any => Some(Language::English),
+            })
+            .unwrap_or(Language::English)
+    }
+}
+
+impl Error {
+    /// Message shown to users. Errors that are not the client's fault are
+    /// always reported in English.
+    fn message(&self, language: Language) -> String {
+        use Error::*;
+        match (language, self) {
+            (Language::Norwegian, NotFound(id)) => {
+                format!("datasett med FDK ID '{id}' finnes ikke")
+            }
+            (Language::Norwegian, DatasetNotScored(uri)) => {
+                format!("datasett '{uri}' har ingen lagret poengsum")
+            }
+            (Language::Norwegian, InvalidID(id)) => format!("ugyldig FDK ID: '{id}'"),
+            (Language::Norwegian, InvalidRequest(_)) => "ugyldig forespørsel".to_string(),
+            (Language::Norwegian, DimensionMismatch(_)) => {
+                "dimensjons-ID-er som ikke er referert i vurderingsgrafen".to_string()
+            }
+            (Language::Norwegian, DuplicateDimensions(_)) => {
+                "dupliserte dimensjons-ID-er".to_string()
+            }
+            (Language::Norwegian, ScoreOutOfRange(_)) => {
+                "poengsummer utenfor gyldig område".to_string()
+            }
+            (Language::Norwegian, InvalidScore(_)) => {
+                "dimensjonspoengsummer som overstiger maksimal poengsum".to_string()
+            }
+            (Language::Norwegian, ScoreMismatch(_)) => {
+                "dimensjonspoengsummer som avviker fra vurderingsgrafen".to_string()
+            }
+            (Language::Norwegian, PercentileOutOfRange(_)) => {
+                "persentiler utenfor gyldig område".to_string()
+            }
+            (Language::Norwegian, DatasetConflict(_)) => {
+                "datasettet er allerede vurdert under en annen FDK ID".to_string()
+            }
+            (Language::Norwegian, NotAcceptable(offered)) => format!(
+                "ingen av de aksepterte medietypene er tilgjengelige, forventet en av {}",
+                offered.join(", ")
+            ),
+            (Language::Norwegian, Unauthorized(_)) => {
+                "Ikke autorisert: manglende eller ugyldig API-nøkkel".to_string()
+            }
+            _ => self.to_string(),
+        }
+    }
+
+    /// Fields failing validation, for the errors that list them.
+    pub fn field_errors(&self) -> Option<&[FieldError]> {
+        use Error::*;
+        match self {
+            InvalidRequest(errors)
+            | InvalidScore(errors)
+            | PercentileOutOfRange(errors)
+            | DimensionMismatch(errors)
+            | DuplicateDimensions(errors)
+            | ScoreOutOfRange(errors)
+            | ScoreMismatch(errors)
+            | DatasetConflict(errors) => Some(errors),
+            _ => None,
+        }
+    }
+
+    pub fn localized_response(&self, language: Language) -> HttpResponse {
         use Error::*;
+        let message = self.message(language);
         match self {
-            NotFound(_) => HttpResponse::NotFound().json(ErrorReply::message(self)),
-            InvalidID(_) => HttpResponse::BadRequest().json(ErrorReply::error(self)),
-            InvalidUri(_) => HttpResponse::BadRequest().json(ErrorReply::error(self)),
-            Unauthorized(_) => HttpResponse::Unauthorized().json(ErrorReply::error(self)),
+            NotFound(_) | DatasetNotScored(_) => {
+                HttpResponse::NotFound().json(ErrorReply::message(message))
+            }
+            InvalidID(_) => HttpResponse::BadRequest().json(ErrorReply::validation(
+                &message,
+                vec![FieldError::new("id".to_string(), message.clone())],
+            )),
+            InvalidRequest(errors) | InvalidScore(errors) | PercentileOutOfRange(errors) => {
+                HttpResponse::BadRequest().json(ErrorReply::validation(message, errors.clone()))
+            }
+            // Malformed requests, reported against the part that failed to parse.
+            InvalidGraph(_) | GraphParseTimeout(_) | Utf8Error(_) | SerdeJsonError(_) => {
+                HttpResponse::BadRequest().json(ErrorReply::validation(
+                    &message,
+                    vec![FieldError::new("body".to_string(), message.clone())],
+                ))
+            }
+            InvalidUri(_) => HttpResponse::BadRequest().json(ErrorReply::validation(
+                &message,
+                vec![FieldError::new("uri".to_string(), message.clone())],
+            )),
+            DimensionMismatch(errors)
+            | DuplicateDimensions(errors)
+            | ScoreOutOfRange(errors)
+            | ScoreMismatch(errors) => HttpResponse::UnprocessableEntity()
+                .json(ErrorReply::validation(message, errors.clone())),
+            DatasetConflict(errors) => {
+                HttpResponse::Conflict().json(ErrorReply::validation(message, errors.clone()))
+            }
+            NotAcceptable(_) => HttpResponse::NotAcceptable().json(ErrorReply::error(message)),
+            GraphTooLarge(..) => HttpResponse::PayloadTooLarge().json(ErrorReply::error(message)),
+            Unauthorized(_) => HttpResponse::Unauthorized().json(ErrorReply::error(message)),
+            Forbidden(_) => HttpResponse::Forbidden().json(ErrorReply::error(message)),
+            WriteFrozen(seconds) => HttpResponse::ServiceUnavailable()
+                .insert_header((header::RETRY_AFTER, seconds.to_string()))
+                .json(ErrorReply::error(message)),
             _ => {
                 tracing::error!(
                     error = format!("{:?}", self).as_str(),
                     "error occured when processing request"
                 );
-                HttpResponse::InternalServerError().json(ErrorReply::error(self))
+                HttpResponse::InternalServerError().json(ErrorReply::error(message))
             }
         }
     }
@@ -48,6 +205,8 @@ impl ResponseError for Error {
 pub struct ErrorReply {
     message: Option<String>,
     error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    errors: Option<Vec<FieldError>>,
 }
 
 impl ErrorReply {
@@ -63,4 +222,11 @@ impl ErrorReply {
             ..Default::default()
         }
     }
+    fn validation<S: ToString>(error: S, errors: Vec<FieldError>) -> Self {
+        ErrorReply {
+            error: Some(error.to_string()),
+            errors: Some(errors),
+            ..Default::default()
+        }
+    }
 }
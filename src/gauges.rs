@@ -0,0 +1,78 @@
+use std::{
+    fmt::Write,
+    sync::{Arc, RwLock},
+};
+
+use crate::models::DimensionOverview;
+
+/// Latest store-wide dimension aggregates shared by all workers, rendered as
+/// Prometheus gauges on `/metrics`.
+#[derive(Clone, Default)]
+pub struct DimensionGauges(Arc<RwLock<Vec<DimensionOverview>>>);
+
+impl DimensionGauges {
+    pub fn set(&self, overview: Vec<DimensionOverview>) {
+        *self.0.write().unwrap() = overview;
+    }
+
+    /// The gauges in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let overview = self.0.read().unwrap();
+        let mut text = String::new();
+
+        text.push_str("# HELP mqa_dimension_avg_ratio Average share of the max score reached per dimension.\n");
+        text.push_str("# TYPE mqa_dimension_avg_ratio gauge\n");
+        for dimension in overview.iter() {
+            let _ = writeln!(
+                text,
+                "mqa_dimension_avg_ratio{{dimension=\"{}\"}} {}",
+                escape(&dimension.id),
+                dimension.average_ratio
+            );
+        }
+        text.push_str(
+            "# HELP mqa_dimension_dataset_count Number of datasets scored per dimension.\n",
+        );
+        text.push_str("# TYPE mqa_dimension_dataset_count gauge\n");
+        for dimension in overview.iter() {
+            let _ = writeln!(
+                text,
+                "mqa_dimension_dataset_count{{dimension=\"{}\"}} {}",
+                escape(&dimension.id),
+                dimension.dataset_count
+            );
+        }
+        text
+    }
+}
+
+/// Escapes a label value as required by the text exposition format.
+pub fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let gauges = DimensionGauges::default();
+        gauges.set(vec![DimensionOverview::new(
+            "https://dimension#\"a\"".to_string(),
+            0.25,
+            4,
+        )]);
+
+        let text = gauges.render();
+        assert!(text.contains("# TYPE mqa_dimension_avg_ratio gauge\n"));
+        assert!(text
+            .contains("mqa_dimension_avg_ratio{dimension=\"https://dimension#\\\"a\\\"\"} 0.25\n"));
+        assert!(text.contains(
+            "mqa_dimension_dataset_count{dimension=\"https://dimension#\\\"a\\\"\"} 4\n"
+        ));
+    }
+}
@@ -0,0 +1,821 @@
+use std::{
+    collections::{HashMap, HashSet},
+    str::from_utf8,
+};
+
+use oxigraph::{
+    io::{JsonLdProfileSet, RdfFormat, RdfParser, RdfSerializer},
+    model::{
+        graph::CanonicalizationAlgorithm, BlankNode, Dataset, Graph, GraphNameRef, NamedNode,
+        NamedNodeRef, Quad, QuadRef, Subject, Term, TripleRef,
+    },
+    store::Store,
+};
+use spargebra::{
+    term::{GroundTermPattern, NamedNodePattern},
+    GraphUpdateOperation, Update,
+};
+
+use crate::{
+    error::Error,
+    models::{DatasetScore, FieldError},
+    vocab::{self, dct, dqv, mqa},
+};
+
+/// Dimension IRIs referenced with `dqv:inDimension` in a Turtle graph.
+pub fn referenced_dimensions(turtle: &str) -> Result<HashSet<String>, Error> {
+    let mut dimensions = HashSet::new();
+    for quad in RdfParser::from_format(RdfFormat::Turtle).for_slice(turtle.as_bytes()) {
+        let quad = quad.map_err(|e| Error::InvalidGraph(e.to_string()))?;
+        if quad.predicate != dqv::IN_DIMENSION {
+            continue;
+        }
+        if let Term::NamedNode(dimension) = quad.object {
+            dimensions.insert(dimension.into_string());
+        }
+    }
+    Ok(dimensions)
+}
+
+/// Publisher of `dataset_uri` in a Turtle graph, the first IRI it refers to with
+/// `dct:publisher`.
+pub fn publisher(turtle: &str, dataset_uri: &str) -> Result<Option<String>, Error> {
+    for quad in RdfParser::from_format(RdfFormat::Turtle).for_slice(turtle.as_bytes()) {
+        let quad = quad.map_err(|e| Error::InvalidGraph(e.to_string()))?;
+        if quad.predicate != dct::PUBLISHER
+            || !matches!(&quad.subject, Subject::NamedNode(node) if node.as_str() == dataset_uri)
+        {
+            continue;
+        }
+        if let Term::NamedNode(publisher) = quad.object {
+            return Ok(Some(publisher.into_string()));
+        }
+    }
+    Ok(None)
+}
+
+/// Dimension scores of `dataset_uri` in a Turtle graph, read from the values of
+/// the `<dimension>Scoring` measurements computed on the dataset.
+pub fn dimension_scores(turtle: &str, dataset_uri: &str) -> Result<HashMap<String, i32>, Error> {
+    #[derive(Default)]
+    struct Measurement {
+        metric: Option<String>,
+        value: Option<i32>,
+        computed_on: Option<String>,
+    }
+
+    let mut measurements: HashMap<String, Measurement> = HashMap::new();
+    for quad in RdfParser::from_format(RdfFormat::Turtle).for_slice(turtle.as_bytes()) {
+        let quad = quad.map_err(|e| Error::InvalidGraph(e.to_string()))?;
+        let measurement = measurements.entry(quad.subject.to_string()).or_default();
+        match quad.object {
+            Term::NamedNode(node) if quad.predicate == dqv::IS_MEASUREMENT_OF => {
+                measurement.metric = Some(node.into_string())
+            }
+            Term::NamedNode(node) if quad.predicate == dqv::COMPUTED_ON => {
+                measurement.computed_on = Some(node.into_string())
+            }
+            Term::Literal(value) if quad.predicate == dqv::VALUE => {
+                measurement.value = value.value().parse().ok()
+            }
+            _ => {}
+        }
+    }
+
+    Ok(measurements
+        .into_values()
+        .filter(|m| m.computed_on.as_deref() == Some(dataset_uri))
+        .filter_map(|m| {
+            let dimension = m
+                .metric?
+                .strip_suffix(mqa::DIMENSION_SCORING_SUFFIX)?
+                .to_string();
+            Some((dimension, m.value?))
+        })
+        .collect())
+}
+
+pub const JSON_LD: RdfFormat = RdfFormat::JsonLd {
+    profile: JsonLdProfileSet::empty(),
+};
+
+/// First error encountered when parsing `graph` in the given format, if any.
+pub fn parse_error(format: RdfFormat, graph: &str) -> Option<String> {
+    RdfParser::from_format(format)
+        .for_slice(graph.as_bytes())
+        .find_map(|quad| quad.err())
+        .map(|e| e.to_string())
+}
+
+/// Subgraph of `graph` without the measurements of other dimensions than `dimension`.
+///
+/// A measurement belongs to the dimension its metric is `dqv:inDimension`. Triples
+/// about, or pointing at, measurements and metrics of other dimensions are dropped,
+/// the rest (such as the dataset and distribution assessment nodes) are kept.
+pub fn filter_dimension(
+    format: RdfFormat,
+    graph: &str,
+    dimension: NamedNodeRef<'_>,
+) -> Result<String, Error> {
+    let quads = RdfParser::from_format(format)
+        .for_slice(graph.as_bytes())
+        .collect::<Result<Vec<Quad>, _>>()
+        .map_err(|e| Error::InvalidGraph(e.to_string()))?;
+
+    let mut metrics = HashSet::new();
+    let mut excluded = HashSet::new();
+    for quad in quads.iter().filter(|q| q.predicate == dqv::IN_DIMENSION) {
+        if quad.object == dimension.into() {
+            metrics.insert(Term::from(quad.subject.clone()));
+        } else {
+            excluded.insert(Term::from(quad.subject.clone()));
+        }
+    }
+    for quad in quads
+        .iter()
+        .filter(|q| q.predicate == dqv::IS_MEASUREMENT_OF)
+    {
+        if !metrics.contains(&quad.object) {
+            excluded.insert(Term::from(quad.subject.clone()));
+        }
+    }
+
+    let mut serializer = RdfSerializer::from_format(format).for_writer(Vec::new());
+    for quad in quads.iter().filter(|q| {
+        !excluded.contains(&Term::from(q.subject.clone())) && !excluded.contains(&q.object)
+    }) {
+        serializer
+            .serialize_quad(quad)
+            .map_err(|e| Error::InvalidGraph(e.to_string()))?;
+    }
+    let bytes = serializer
+        .finish()
+        .map_err(|e| Error::InvalidGraph(e.to_string()))?;
+    Ok(from_utf8(&bytes)?.to_string())
+}
+
+/// Maximum number of operations in a SPARQL update applied to an assessment.
+pub const MAX_UPDATE_OPERATIONS: usize = 16;
+
+fn invalid_update(message: impl ToString) -> Error {
+    Error::InvalidRequest(vec![FieldError::new(
+        "update".to_string(),
+        message.to_string(),
+    )])
+}
+
+/// Applies a SPARQL update to a Turtle graph, returning the default graph as
+/// Turtle and JSON-LD. Graph management operations, deletes of arbitrary
+/// triples (`DELETE { ?s ?p ?o }`) and updates of more than
+/// [`MAX_UPDATE_OPERATIONS`] operations are rejected.
+pub fn apply_update(turtle: &str, update: &str) -> Result<(String, String), Error> {
+    let update = Update::parse(update, None).map_err(invalid_update)?;
+    if update.operations.len() > MAX_UPDATE_OPERATIONS {
+        return Err(invalid_update(format!(
+            "at most {MAX_UPDATE_OPERATIONS} operations are allowed"
+        )));
+    }
+    for operation in &update.operations {
+        match operation {
+            GraphUpdateOperation::InsertData { .. } | GraphUpdateOperation::DeleteData { .. } => {}
+            GraphUpdateOperation::DeleteInsert { delete, .. } => {
+                let unbounded = delete.iter().any(|quad| {
+                    matches!(quad.subject, GroundTermPattern::Variable(_))
+                        && matches!(quad.predicate, NamedNodePattern::Variable(_))
+                        && matches!(quad.object, GroundTermPattern::Variable(_))
+                });
+                if unbounded {
+                    return Err(invalid_update(
+                        "deleted triples must fix a subject, predicate or object",
+                    ));
+                }
+            }
+            _ => {
+                return Err(invalid_update(
+                    "LOAD, CLEAR, CREATE and DROP are not allowed",
+                ))
+            }
+        }
+    }
+
+    let store = Store::new().map_err(|e| Error::InvalidGraph(e.to_string()))?;
+    store
+        .load_from_reader(RdfFormat::Turtle, turtle.as_bytes())
+        .map_err(|e| Error::InvalidGraph(e.to_string()))?;
+    store
+        .update(oxigraph::sparql::Update::from(update))
+        .map_err(invalid_update)?;
+
+    let dump = |format: RdfFormat| -> Result<String, Error> {
+        let bytes = store
+            .dump_graph_to_writer(GraphNameRef::DefaultGraph, format, Vec::new())
+            .map_err(|e| Error::InvalidGraph(e.to_string()))?;
+        Ok(from_utf8(&bytes)?.to_string())
+    };
+    Ok((dump(RdfFormat::Turtle)?, dump(JSON_LD)?))
+}
+
+/// Quads of every Turtle graph by dataset uri, ordered by dataset uri. Graphs
+/// that fail to parse are left out, so one corrupt row cannot fail a bulk
+/// fetch; their dataset uris are returned.
+fn parse_graphs(
+    format: RdfFormat,
+    graphs: &HashMap<String, String>,
+) -> (Vec<(&String, Vec<Quad>)>, Vec<String>) {
+    let mut dataset_uris: Vec<&String> = graphs.keys().collect();
+    dataset_uris.sort();
+
+    let mut parsed = Vec::new();
+    let mut skipped = Vec::new();
+    for dataset_uri in dataset_uris {
+        // Blank node labels are only unique within a single stored graph.
+        let parser = RdfParser::from_format(format).rename_blank_nodes();
+        match parser
+            .for_slice(graphs[dataset_uri].as_bytes())
+            .collect::<Result<Vec<Quad>, _>>()
+        {
+            Ok(quads) => parsed.push((dataset_uri, quads)),
+            Err(e) => {
+                tracing::warn!(
+                    dataset_uri = dataset_uri.as_str(),
+                    error = e.to_string().as_str(),
+                    "skipping unparsable graph"
+                );
+                skipped.push(dataset_uri.clone());
+            }
+        }
+    }
+    (parsed, skipped)
+}
+
+/// TriG document with every Turtle graph in a named graph of its dataset uri,
+/// ordered by dataset uri, and the dataset uris of graphs that did not parse.
+pub fn trig_dataset(graphs: &HashMap<String, String>) -> Result<(String, Vec<String>), Error> {
+    let (parsed, skipped) = parse_graphs(RdfFormat::Turtle, graphs);
+
+    let mut serializer = RdfSerializer::from_format(RdfFormat::TriG).for_writer(Vec::new());
+    for (dataset_uri, quads) in parsed {
+        let graph_name =
+            NamedNode::new(dataset_uri).map_err(|e| Error::InvalidGraph(e.to_string()))?;
+        for quad in quads {
+            serializer
+                .serialize_quad(QuadRef::new(
+                    &quad.subject,
+                    &quad.predicate,
+                    &quad.object,
+                    &graph_name,
+                ))
+                .map_err(|e| Error::InvalidGraph(e.to_string()))?;
+        }
+    }
+    let bytes = serializer
+        .finish()
+        .map_err(|e| Error::InvalidGraph(e.to_string()))?;
+    Ok((from_utf8(&bytes)?.to_string(), skipped))
+}
+
+/// Union of the Turtle graphs as a single Turtle document, re-serialized so
+/// their prefix declarations cannot clash, and the dataset uris of graphs that
+/// did not parse.
+pub fn merged_turtle(graphs: &HashMap<String, String>) -> Result<(String, Vec<String>), Error> {
+    let (merged, skipped) = merge(RdfFormat::Turtle, graphs);
+    let bytes = serialize_graph(RdfFormat::Turtle, &merged)?;
+    Ok((from_utf8(&bytes)?.to_string(), skipped))
+}
+
+/// Union of the JSON-LD graphs as a single JSON-LD document with a shared
+/// `@context`, and the dataset uris of graphs that did not parse.
+pub fn merged_jsonld(graphs: &HashMap<String, String>) -> Result<(String, Vec<String>), Error> {
+    let (merged, skipped) = merge(JSON_LD, graphs);
+    let nodes: serde_json::Value = serde_json::from_slice(&serialize_graph(JSON_LD, &merged)?)?;
+    let context: serde_json::Map<String, serde_json::Value> = vocab::JSON_LD_PREFIXES
+        .iter()
+        .map(|(prefix, iri)| (prefix.to_string(), iri.to_string().into()))
+        .collect();
+    let document = serde_json::json!({ "@context": context, "@graph": nodes });
+    Ok((serde_json::to_string(&document)?, skipped))
+}
+
+/// Union of the graphs that parse in the given format, and the dataset uris of
+/// those that did not.
+fn merge(format: RdfFormat, graphs: &HashMap<String, String>) -> (Graph, Vec<String>) {
+    let (parsed, skipped) = parse_graphs(format, graphs);
+
+    let mut merged = Graph::new();
+    for (_, quads) in &parsed {
+        for quad in quads {
+            merged.insert(TripleRef::new(&quad.subject, &quad.predicate, &quad.object));
+        }
+    }
+    (merged, skipped)
+}
+
+fn serialize_graph(format: RdfFormat, graph: &Graph) -> Result<Vec<u8>, Error> {
+    let mut serializer = RdfSerializer::from_format(format).for_writer(Vec::new());
+    for triple in graph.iter() {
+        serializer
+            .serialize_triple(triple)
+            .map_err(|e| Error::InvalidGraph(e.to_string()))?;
+    }
+    serializer
+        .finish()
+        .map_err(|e| Error::InvalidGraph(e.to_string()))
+}
+
+/// The graph as sorted N-Quads with canonical blank node labels, equal for
+/// graphs differing only in serialization format, prefixes, formatting, quad
+/// order or blank node labels. Quads in the default graph are plain N-Triples.
+pub fn canonical_nquads(format: RdfFormat, graph: &str) -> Result<String, Error> {
+    let mut dataset = Dataset::new();
+    for quad in RdfParser::from_format(format).for_slice(graph.as_bytes()) {
+        let quad = quad.map_err(|e| Error::InvalidGraph(e.to_string()))?;
+        dataset.insert(&quad);
+    }
+    dataset.canonicalize(CanonicalizationAlgorithm::Unstable);
+
+    let mut lines: Vec<String> = dataset.iter().map(|quad| format!("{quad} .")).collect();
+    lines.sort();
+    Ok(lines.join("\n"))
+}
+
+/// The Turtle graph as an RDF/XML document.
+pub fn rdf_xml(turtle: &str) -> Result<String, Error> {
+    // Blank node labels are written as rdf:nodeID, which must be an XML name
+    // and so cannot start with a digit like the stored labels may.
+    let relabel = |node: BlankNode| BlankNode::new_unchecked(format!("b{}", node.as_str()));
+    let mut graph = Graph::new();
+    for triple in turtle_graph(turtle)?.iter() {
+        let triple = triple.into_owned();
+        let subject = match triple.subject {
+            Subject::BlankNode(node) => Subject::BlankNode(relabel(node)),
+            subject => subject,
+        };
+        let object = match triple.object {
+            Term::BlankNode(node) => Term::BlankNode(relabel(node)),
+            object => object,
+        };
+        graph.insert(TripleRef::new(&subject, &triple.predicate, &object));
+    }
+    let bytes = serialize_graph(RdfFormat::RdfXml, &graph)?;
+    Ok(from_utf8(&bytes)?.to_string())
+}
+
+fn turtle_graph(turtle: &str) -> Result<Graph, Error> {
+    let mut graph = Graph::new();
+    for quad in RdfParser::from_format(RdfFormat::Turtle).for_slice(turtle.as_bytes()) {
+        let quad = quad.map_err(|e| Error::InvalidGraph(e.to_string()))?;
+        graph.insert(TripleRef::new(&quad.subject, &quad.predicate, &quad.object));
+    }
+    Ok(graph)
+}
+
+/// Checks that every dimension scored in the JSON is referenced by the graph,
+/// reporting each dimension that is not as a field error.
+pub fn validate_dimension_ids(turtle: &str, scores: &DatasetScore) -> Result<(), Error> {
+    let referenced = referenced_dimensions(turtle)?;
+
+    let scored = scores
+        .dataset
+        .dimensions
+        .iter()
+        .enumerate()
+        .map(|(i, dimension)| (format!("scores.dataset.dimensions[{i}].id"), &dimension.id))
+        .chain(
+            scores
+                .distributions
+                .iter()
+                .enumerate()
+                .flat_map(|(i, distribution)| {
+                    distribution
+                        .dimensions
+                        .iter()
+                        .enumerate()
+                        .map(move |(j, dimension)| {
+                            (
+                                format!("scores.distributions[{i}].dimensions[{j}].id"),
+                                &dimension.id,
+                            )
+                        })
+                }),
+        );
+    let errors: Vec<FieldError> = scored
+        .filter(|(_, id)| !referenced.contains(*id))
+        .map(|(field, id)| {
+            FieldError::new(
+                field,
+                format!("dimension '{id}' is not referenced with dqv:inDimension in the graph"),
+            )
+        })
+        .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::DimensionMismatch(errors))
+    }
+}
+
+/// Checks the dataset dimension scores against the `<dimension>Scoring`
+/// measurements of the Turtle graph, which they are meant to mirror.
+pub fn verify_dimension_scores(turtle: &str, scores: &DatasetScore) -> Result<(), Error> {
+    let mut in_graph = dimension_scores(turtle, &scores.dataset.id)?;
+
+    let mut errors = Vec::new();
+    for (i, dimension) in scores.dataset.dimensions.iter().enumerate() {
+        let field = format!("scores.dataset.dimensions[{i}].score");
+        match in_graph.remove(&dimension.id) {
+            Some(score) if score == dimension.score => {}
+            Some(score) => errors.push(FieldError::new(
+                field,
+                format!(
+                    "the graph scores dimension '{}' {score}, not {}",
+                    dimension.id, dimension.score
+                ),
+            )),
+            None => errors.push(FieldError::new(
+                field,
+                format!("the graph has no score for dimension '{}'", dimension.id),
+            )),
+        }
+    }
+    let mut unlisted: Vec<String> = in_graph.into_keys().collect();
+    unlisted.sort();
+    errors.extend(unlisted.into_iter().map(|id| {
+        FieldError::new(
+            "scores.dataset.dimensions".to_string(),
+            format!("dimension '{id}' is scored in the graph but not listed"),
+        )
+    }));
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::ScoreMismatch(errors))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Dimension, Score};
+
+    const TURTLE: &str = r#"
+        @prefix dqv: <http://www.w3.org/ns/dqv#> .
+        <https://metric.a> dqv:inDimension <https://dimension#a> .
+        <https://metric.b> dqv:inDimension <https://dimension#b> .
+    "#;
+
+    fn scores(dataset: &[&str], distribution: &[&str]) -> DatasetScore {
+        let score = |dimensions: &[&str]| {
+            Score::new(
+                "https://dataset.foo".to_string(),
+                dimensions
+                    .iter()
+                    .map(|id| Dimension::new(id.to_string(), vec![], 0, 0))
+                    .collect(),
+                0,
+                0,
+            )
+        };
+        DatasetScore::new(score(dataset), vec![score(distribution)])
+    }
+
+    #[test]
+    fn test_referenced_dimensions() {
+        assert_eq!(
+            referenced_dimensions(TURTLE).unwrap(),
+            HashSet::from([
+                "https://dimension#a".to_string(),
+                "https://dimension#b".to_string()
+            ])
+        );
+        assert!(matches!(
+            referenced_dimensions("<https://a> <https://b>"),
+            Err(Error::InvalidGraph(_))
+        ));
+    }
+
+    #[test]
+    fn test_dimension_scores() {
+        let turtle = r#"
+            @prefix dqv: <http://www.w3.org/ns/dqv#> .
+            @prefix mqa: <https://data.norge.no/vocabulary/dcatno-mqa#> .
+            _:a dqv:isMeasurementOf mqa:accessibilityScoring ;
+                dqv:computedOn <https://dataset.foo> ; dqv:value 70 .
+            _:b dqv:isMeasurementOf mqa:accessibilityScoring ;
+                dqv:computedOn <https://distribution.foo> ; dqv:value 50 .
+            _:c dqv:isMeasurementOf mqa:accessUrlStatusCode ;
+                dqv:computedOn <https://dataset.foo> ; dqv:value 200 .
+        "#;
+        assert_eq!(
+            dimension_scores(turtle, "https://dataset.foo").unwrap(),
+            HashMap::from([(
+                "https://data.norge.no/vocabulary/dcatno-mqa#accessibility".to_string(),
+                70
+            )])
+        );
+    }
+
+    #[test]
+    fn test_merged_turtle() {
+        // The same prefix bound to different namespaces, and a shared blank node label.
+        let graphs = HashMap::from([
+            (
+                "https://dataset.a".to_string(),
+                "@prefix p: <https://a#> . p:s p:o _:x .".to_string(),
+            ),
+            (
+                "https://dataset.b".to_string(),
+                "@prefix p: <https://b#> . p:s p:o _:x .".to_string(),
+            ),
+            ("https://dataset.c".to_string(), "not turtle".to_string()),
+        ]);
+        let (turtle, skipped) = merged_turtle(&graphs).unwrap();
+        assert_eq!(skipped, vec!["https://dataset.c".to_string()]);
+
+        let triples: Vec<Quad> = RdfParser::from_format(RdfFormat::Turtle)
+            .for_slice(turtle.as_bytes())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(triples.len(), 2);
+        let subjects: HashSet<String> = triples.iter().map(|t| t.subject.to_string()).collect();
+        assert_eq!(
+            subjects,
+            HashSet::from(["<https://a#s>".to_string(), "<https://b#s>".to_string()])
+        );
+        assert_ne!(triples[0].object, triples[1].object);
+    }
+
+    #[test]
+    fn test_merged_jsonld() {
+        let graphs = HashMap::from([
+            (
+                "https://dataset.a".to_string(),
+                r#"[{"@id":"_:m","http://www.w3.org/ns/dqv#computedOn":[{"@id":"https://dataset.a"}],"http://www.w3.org/ns/dqv#value":[{"@value":"50","@type":"http://www.w3.org/2001/XMLSchema#integer"}]}]"#.to_string(),
+            ),
+            (
+                "https://dataset.b".to_string(),
+                r#"[{"@id":"_:m","http://www.w3.org/ns/dqv#computedOn":[{"@id":"https://dataset.b"}],"http://www.w3.org/ns/dqv#value":[{"@value":true}]}]"#.to_string(),
+            ),
+            ("https://dataset.c".to_string(), "[{".to_string()),
+        ]);
+        let (jsonld, skipped) = merged_jsonld(&graphs).unwrap();
+        assert_eq!(skipped, vec!["https://dataset.c".to_string()]);
+
+        let document: serde_json::Value = serde_json::from_str(&jsonld).unwrap();
+        assert_eq!(
+            document["@context"]["dqv"],
+            serde_json::json!("http://www.w3.org/ns/dqv#")
+        );
+
+        let parse = |jsonld: &str| {
+            let mut graph = Graph::new();
+            for quad in RdfParser::from_format(JSON_LD)
+                .rename_blank_nodes()
+                .for_slice(jsonld.as_bytes())
+            {
+                let quad = quad.unwrap();
+                graph.insert(TripleRef::new(&quad.subject, &quad.predicate, &quad.object));
+            }
+            graph
+        };
+        let mut expected = Graph::new();
+        for dataset_uri in ["https://dataset.a", "https://dataset.b"] {
+            expected.extend(parse(&graphs[dataset_uri]).iter());
+        }
+        let mut merged = parse(&jsonld);
+        assert_eq!(merged.len(), 4);
+        expected.canonicalize(CanonicalizationAlgorithm::Unstable);
+        merged.canonicalize(CanonicalizationAlgorithm::Unstable);
+        assert_eq!(merged, expected);
+    }
+
+    #[test]
+    fn test_merged_jsonld_empty() {
+        let (jsonld, skipped) = merged_jsonld(&HashMap::new()).unwrap();
+        assert!(skipped.is_empty());
+
+        let document: serde_json::Value = serde_json::from_str(&jsonld).unwrap();
+        assert_eq!(document["@graph"], serde_json::json!([]));
+        assert_eq!(parse_error(JSON_LD, &jsonld), None);
+    }
+
+    #[test]
+    fn test_canonical_nquads() {
+        let a = r#"
+            @prefix ex: <https://example.com/> .
+            ex:s ex:p _:x ; ex:q "v" .
+            _:x ex:p ex:o .
+        "#;
+        let b = r#"
+            <https://example.com/s> <https://example.com/q> "v" .
+            _:other <https://example.com/p> <https://example.com/o> .
+            <https://example.com/s> <https://example.com/p> _:other .
+        "#;
+        let c = r#"[
+            { "@id": "_:y", "https://example.com/p": { "@id": "https://example.com/o" } },
+            {
+                "@id": "https://example.com/s",
+                "https://example.com/q": "v",
+                "https://example.com/p": { "@id": "_:y" }
+            }
+        ]"#;
+        let canonical = canonical_nquads(RdfFormat::Turtle, a).unwrap();
+        assert_eq!(canonical, canonical_nquads(RdfFormat::Turtle, b).unwrap());
+        assert_eq!(canonical, canonical_nquads(JSON_LD, c).unwrap());
+        assert_ne!(
+            canonical,
+            canonical_nquads(RdfFormat::Turtle, &b.replace("\"v\"", "\"w\"")).unwrap()
+        );
+        assert!(canonical_nquads(RdfFormat::Turtle, "not turtle").is_err());
+    }
+
+    #[test]
+    fn test_publisher() {
+        let turtle = r#"
+            @prefix dct: <http://purl.org/dc/terms/> .
+            <https://dataset.foo> dct:publisher <https://publisher.foo> .
+            <https://dataset.bar> dct:publisher <https://publisher.bar> .
+        "#;
+        assert_eq!(
+            publisher(turtle, "https://dataset.foo").unwrap(),
+            Some("https://publisher.foo".to_string())
+        );
+        assert_eq!(publisher(turtle, "https://dataset.baz").unwrap(), None);
+    }
+
+    #[test]
+    fn test_apply_update() {
+        let (turtle, jsonld) = apply_update(
+            TURTLE,
+            "PREFIX dqv: <http://www.w3.org/ns/dqv#>
+             DELETE DATA { <https://metric.b> dqv:inDimension <https://dimension#b> } ;
+             INSERT DATA { <https://metric.c> dqv:inDimension <https://dimension#c> }",
+        )
+        .unwrap();
+        assert_eq!(
+            referenced_dimensions(&turtle).unwrap(),
+            HashSet::from([
+                "https://dimension#a".to_string(),
+                "https://dimension#c".to_string()
+            ])
+        );
+        assert_eq!(parse_error(JSON_LD, &jsonld), None);
+
+        for rejected in [
+            "DELETE WHERE { ?s ?p ?o }",
+            "CLEAR DEFAULT",
+            "LOAD <https://example.com/graph.ttl>",
+            "INSERT DATA { <https://a> <https://b> <https://c> ",
+        ] {
+            assert!(
+                matches!(
+                    apply_update(TURTLE, rejected),
+                    Err(Error::InvalidRequest(_))
+                ),
+                "{rejected}"
+            );
+        }
+        let many = vec!["INSERT DATA { <https://a> <https://b> <https://c> }"; 17].join(" ;\n");
+        assert!(matches!(
+            apply_update(TURTLE, &many),
+            Err(Error::InvalidRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(parse_error(RdfFormat::Turtle, TURTLE), None);
+        assert!(parse_error(RdfFormat::Turtle, "<https://a> <https://b>").is_some());
+        assert_eq!(
+            parse_error(
+                JSON_LD,
+                r#"{"@id": "https://a", "https://b": {"@id": "https://c"}}"#
+            ),
+            None
+        );
+        assert!(parse_error(JSON_LD, r#"{"@id": "#).is_some());
+    }
+
+    #[test]
+    fn test_filter_dimension() {
+        let graph = r#"
+            @prefix dqv: <http://www.w3.org/ns/dqv#> .
+            @prefix mqa: <https://data.norge.no/vocabulary/dcatno-mqa#> .
+            <https://assessment> mqa:containsQualityMeasurement _:a, _:b .
+            _:a dqv:isMeasurementOf <https://metric.a> ; dqv:value 1 .
+            _:b dqv:isMeasurementOf <https://metric.b> ; dqv:value 2 .
+            <https://metric.a> dqv:inDimension <https://dimension#a> .
+            <https://metric.b> dqv:inDimension <https://dimension#b> .
+        "#;
+
+        let filtered = filter_dimension(
+            RdfFormat::Turtle,
+            graph,
+            NamedNodeRef::new_unchecked("https://dimension#a"),
+        )
+        .unwrap();
+        let metrics: HashSet<String> = RdfParser::from_format(RdfFormat::Turtle)
+            .for_slice(filtered.as_bytes())
+            .map(|quad| quad.unwrap())
+            .filter(|quad| quad.predicate == dqv::IS_MEASUREMENT_OF)
+            .map(|quad| quad.object.to_string())
+            .collect();
+        assert_eq!(metrics, HashSet::from(["<https://metric.a>".to_string()]));
+        assert!(filtered.contains("https://assessment"));
+        assert!(!filtered.contains("https://dimension#b"));
+        assert_eq!(
+            RdfParser::from_format(RdfFormat::Turtle)
+                .for_slice(filtered.as_bytes())
+                .count(),
+            4
+        );
+    }
+
+    #[test]
+    fn test_validate_dimension_ids() {
+        assert!(validate_dimension_ids(
+            TURTLE,
+            &scores(
+                &["https://dimension#a", "https://dimension#b"],
+                &["https://dimension#a"]
+            )
+        )
+        .is_ok());
+
+        match validate_dimension_ids(
+            TURTLE,
+            &scores(
+                &["https://dimension#a", "https://dimension#c"],
+                &["https://dimension#d"],
+            ),
+        ) {
+            Err(Error::DimensionMismatch(errors)) => {
+                let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+                assert_eq!(
+                    fields,
+                    [
+                        "scores.dataset.dimensions[1].id",
+                        "scores.distributions[0].dimensions[0].id"
+                    ]
+                );
+            }
+            other => panic!("expected a dimension mismatch, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_verify_dimension_scores() {
+        let turtle = r#"
+            @prefix dqv: <http://www.w3.org/ns/dqv#> .
+            @prefix mqa: <https://data.norge.no/vocabulary/dcatno-mqa#> .
+            _:a dqv:isMeasurementOf mqa:accessibilityScoring ;
+                dqv:computedOn <https://dataset.foo> ; dqv:value 70 .
+            _:b dqv:isMeasurementOf mqa:findabilityScoring ;
+                dqv:computedOn <https://dataset.foo> ; dqv:value 20 .
+        "#;
+        let scored = |dimensions: &[(&str, i32)]| {
+            let mut scores = scores(&[], &[]);
+            scores.dataset.dimensions = dimensions
+                .iter()
+                .map(|(id, score)| {
+                    Dimension::new(
+                        format!("https://data.norge.no/vocabulary/dcatno-mqa#{id}"),
+                        vec![],
+                        *score,
+                        100,
+                    )
+                })
+                .collect();
+            scores
+        };
+        assert!(verify_dimension_scores(
+            turtle,
+            &scored(&[("findability", 20), ("accessibility", 70)])
+        )
+        .is_ok());
+
+        match verify_dimension_scores(
+            turtle,
+            &scored(&[("accessibility", 60), ("interoperability", 0)]),
+        ) {
+            Err(Error::ScoreMismatch(errors)) => {
+                let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+                assert_eq!(
+                    fields,
+                    [
+                        "scores.dataset.dimensions[0].score",
+                        "scores.dataset.dimensions[1].score",
+                        "scores.dataset.dimensions"
+                    ]
+                );
+                assert_eq!(
+                    errors[0].message,
+                    "the graph scores dimension \
+                     'https://data.norge.no/vocabulary/dcatno-mqa#accessibility' 70, not 60"
+                );
+            }
+            other => panic!("expected a score mismatch, got {other:?}"),
+        }
+    }
+}
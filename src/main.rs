@@ -1,41 +1,75 @@
 #[macro_use]
 extern crate diesel;
 #[macro_use]
-extern crate diesel_migrations;
-#[macro_use]
 extern crate serde;
 
-use std::{env, str::from_utf8};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    env,
+    fmt::Display,
+    num::{NonZeroU32, NonZeroUsize},
+    str::from_utf8,
+    str::FromStr,
+    time::{Duration, Instant, SystemTime},
+};
 
-use ::http::{uri::InvalidUri, Uri};
+use ::http::Uri;
 use actix_cors::Cors;
 use actix_web::{
-    body::{BoxBody, EitherBody},
+    body::{BoxBody, MessageBody},
+    delete,
     dev::{ServiceFactory, ServiceRequest, ServiceResponse},
     get,
-    http::header,
-    middleware::Logger,
-    post, web, App, HttpRequest, HttpResponse, HttpServer, Responder,
+    http::{
+        header::{self, Header},
+        Method, StatusCode,
+    },
+    middleware::{from_fn, Compress, Logger, Next, NormalizePath},
+    post, route, web, App, HttpMessage, HttpRequest, HttpResponse, HttpResponseBuilder, HttpServer,
+    Responder, ResponseError,
 };
+use chrono::{DateTime, SubsecRound, Utc};
 use database::migrate_database;
 use lazy_static::lazy_static;
+use oxigraph::{io::RdfFormat, model::NamedNode};
+use prost::Message;
+use sha2::{Digest, Sha256};
+use tracing::Instrument;
 use utoipa::openapi::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 use uuid::Uuid;
 
 use crate::{
-    database::{DatabaseError, PgPool},
+    database::{DatabaseError, GraphStorage, PgPool, QueryCount},
     db_models::{DatasetAssessment, Dimension},
-    error::Error,
-    models::{DatasetsRequest, DatasetsScores},
+    error::{Error, Language},
+    gauges::DimensionGauges,
+    models::{DatasetsRequest, DatasetsScores, FieldError},
+    recent_errors::RecentErrors,
+    request_metrics::RequestMetrics,
+    write_freeze::WriteFreeze,
 };
 
+mod aggregation;
+mod archive;
+mod csv;
 mod database;
 mod db_models;
+mod diff;
 mod error;
-#[allow(dead_code, non_snake_case)]
+mod gauges;
+mod graph;
+#[allow(dead_code, non_snake_case, unused_imports)]
 mod models;
+mod proto;
+mod recent_errors;
+mod redact;
+mod request_metrics;
 mod schema;
+mod signing;
+mod sse;
+mod vocab;
+mod write_freeze;
 
 lazy_static! {
     static ref API_KEY: String = env::var("API_KEY").unwrap_or_else(|e| {
@@ -53,6 +87,168 @@ lazy_static! {
         );
         std::process::exit(1)
     });
+    static ref PERCENT_DECIMALS: u32 = env_or("PERCENT_DECIMALS", 0);
+    static ref MAX_GRAPH_BYTES: usize = env_or("MAX_GRAPH_BYTES", 4_194_304);
+    static ref GRAPH_PARSE_TIMEOUT: Duration =
+        Duration::from_millis(env_or("GRAPH_PARSE_TIMEOUT_MS", 5000));
+    static ref VALIDATE_DIMENSION_IDS: bool = env_or("VALIDATE_DIMENSION_IDS", false);
+    static ref VERIFY_DIMENSION_SCORES: bool = env_or("VERIFY_DIMENSION_SCORES", false);
+    static ref REQUIRE_API_KEY_FOR_READS: bool = env_or("REQUIRE_API_KEY_FOR_READS", false);
+    static ref DUPLICATE_DIMENSION_POLICY: DuplicatePolicy =
+        env_or("DUPLICATE_DIMENSION_POLICY", DuplicatePolicy::Reject);
+    static ref RESPONSE_SIGNING_KEY: Option<String> = env::var("RESPONSE_SIGNING_KEY").ok();
+    static ref METRICS_INTERVAL_SECS: u64 = env_or("METRICS_INTERVAL_SECS", 0);
+    static ref OVERALL_STRATEGY: aggregation::OverallStrategy =
+        env_or("OVERALL_STRATEGY", aggregation::OverallStrategy::Sum);
+    static ref AGGREGATION_BATCH_SIZE: NonZeroUsize =
+        env_or("AGGREGATION_BATCH_SIZE", NonZeroUsize::new(1000).unwrap());
+    static ref RECENT_ERRORS_CAPACITY: usize =
+        env_or("RECENT_ERRORS_CAPACITY", recent_errors::DEFAULT_CAPACITY);
+    static ref REDACT_HEADERS: redact::RedactedHeaders =
+        env_or("REDACT_HEADERS", redact::RedactedHeaders::default());
+    static ref GRAPH_STORAGE: GraphStorage = env_or("GRAPH_STORAGE", GraphStorage::Inline);
+    static ref MAX_REPLICATION_LAG_SECONDS: f64 = env_or("MAX_REPLICATION_LAG_SECONDS", 30.0);
+    static ref PAGE_SIZES: PageSizes = PageSizes::new(
+        env_or("DEFAULT_PAGE_SIZE", NonZeroU32::new(100).unwrap()),
+        env_or("MAX_PAGE_SIZE", NonZeroU32::new(1000).unwrap()),
+    )
+    .unwrap_or_else(|e| {
+        tracing::error!(error = e.as_str(), "DEFAULT_PAGE_SIZE is invalid");
+        std::process::exit(1)
+    });
+}
+
+/// Reads an optional setting, falling back to `default` when unset.
+fn env_or<T: FromStr>(key: &str, default: T) -> T
+where
+    T::Err: Display,
+{
+    match env::var(key) {
+        Ok(value) => value.parse().unwrap_or_else(|e: T::Err| {
+            tracing::error!(error = e.to_string().as_str(), "{} is invalid", key);
+            std::process::exit(1)
+        }),
+        Err(_) => default,
+    }
+}
+
+/// How `update_assessment` treats a dataset dimension id occurring more than once.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum DuplicatePolicy {
+    /// Answer 422 listing the duplicates. The default.
+    Reject,
+    /// Keep the first occurrence of every id.
+    First,
+    /// Keep the last occurrence of every id, in the position of the first.
+    Last,
+}
+
+impl FromStr for DuplicatePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "reject" => Ok(DuplicatePolicy::Reject),
+            "first" => Ok(DuplicatePolicy::First),
+            "last" => Ok(DuplicatePolicy::Last),
+            _ => Err(format!("expected reject, first or last, got '{s}'")),
+        }
+    }
+}
+
+/// Page sizes shared by the paginated endpoints.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct PageSizes {
+    /// Limit of a request without one.
+    default: i64,
+    /// Largest limit served; larger ones are clamped to it.
+    max: i64,
+}
+
+impl PageSizes {
+    fn new(default: NonZeroU32, max: NonZeroU32) -> Result<Self, String> {
+        if default > max {
+            return Err(format!(
+                "expected at most MAX_PAGE_SIZE ({max}), got {default}"
+            ));
+        }
+        Ok(PageSizes {
+            default: default.get().into(),
+            max: max.get().into(),
+        })
+    }
+
+    /// Resolves `limit` and `offset` query parameters, defaulting to the first page.
+    fn page(&self, limit: Option<i64>, offset: Option<i64>) -> Result<(i64, i64), Vec<FieldError>> {
+        let limit = limit.unwrap_or(self.default);
+        let offset = offset.unwrap_or(0);
+
+        let mut errors = Vec::new();
+        if limit < 1 {
+            errors.push(FieldError::new(
+                "limit".to_string(),
+                "must be positive".to_string(),
+            ));
+        }
+        if offset < 0 {
+            errors.push(FieldError::new(
+                "offset".to_string(),
+                "must not be negative".to_string(),
+            ));
+        }
+
+        if errors.is_empty() {
+            Ok((limit.min(self.max), offset))
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// RFC 5988 `Link` header value pointing at the pages of `path` before and
+/// after the one at `offset`, or `None` when the page is the only one.
+fn page_links(path: &str, limit: i64, offset: i64, total: i64) -> Option<String> {
+    let mut links = Vec::new();
+    if offset + limit < total {
+        links.push(format!(
+            "<{path}?limit={limit}&offset={}>; rel=\"next\"",
+            offset + limit
+        ));
+    }
+    if offset > 0 {
+        links.push(format!(
+            "<{path}?limit={limit}&offset={}>; rel=\"prev\"",
+            (offset - limit).max(0)
+        ));
+    }
+    (!links.is_empty()).then(|| links.join(", "))
+}
+
+const TEXT_TURTLE: &str = "text/turtle";
+const APPLICATION_TRIG: &str = "application/trig";
+const APPLICATION_LD_JSON: &str = "application/ld+json";
+const APPLICATION_N_TRIPLES: &str = "application/n-triples";
+const APPLICATION_RDF_XML: &str = "application/rdf+xml";
+
+/// Dataset uris whose stored graphs were left out of a bulk response.
+const SKIPPED_DATASETS_HEADER: &str = "X-Skipped-Datasets";
+/// Id of a request, taken from the client or generated, echoed on the response.
+const REQUEST_ID_HEADER: &str = "X-Request-Id";
+/// Set on a `200` answer to a post whose content matches the stored assessment.
+const NOT_MODIFIED_HEADER: &str = "X-Not-Modified";
+/// Number of items on all pages of a paged listing.
+const TOTAL_COUNT_HEADER: &str = "X-Total-Count";
+
+/// Completes `response` with `body`, adding an `X-Signature` header when
+/// RESPONSE_SIGNING_KEY is set.
+fn signed(mut response: HttpResponseBuilder, body: Vec<u8>) -> HttpResponse {
+    if let Some(key) = RESPONSE_SIGNING_KEY.as_deref() {
+        response.insert_header((
+            signing::SIGNATURE_HEADER,
+            signing::signature(key.as_bytes(), &body),
+        ));
+    }
+    response.body(body)
 }
 
 fn validate_api_key(request: HttpRequest) -> Result<(), Error> {
@@ -70,16 +266,36 @@ fn validate_api_key(request: HttpRequest) -> Result<(), Error> {
     }
 }
 
+/// Whether reading scores and graphs requires the API key as well as writing.
+#[derive(Clone, Copy, Debug, Default)]
+struct ReadAccess {
+    require_api_key: bool,
+}
+
+fn validate_read_access(request: HttpRequest) -> Result<(), Error> {
+    let require_api_key = request
+        .app_data::<web::Data<ReadAccess>>()
+        .is_some_and(|access| access.require_api_key);
+    if require_api_key {
+        validate_api_key(request)
+    } else {
+        Ok(())
+    }
+}
+
 #[get("/ping")]
-async fn ping(pool: web::Data<PgPool>) -> Result<impl Responder, Error> {
+async fn ping(
+    pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
+) -> Result<impl Responder, Error> {
     let result = web::block(move || {
         // Obtaining a connection from the pool is also a potentially blocking operation.
         // So, it should be called within the `web::block` closure, as well.
-        let mut conn = pool.get()?;
+        let mut conn = pool.get(queries.into_inner())?;
         conn.test_connection()
     })
     .await
-    .map_err(|e| Error::BlockingError(e.into()))?;
+    .map_err(Error::BlockingError)?;
 
     match result {
         Ok(_) => Ok("pong"),
@@ -92,312 +308,6932 @@ async fn ready() -> Result<impl Responder, Error> {
     Ok("ok")
 }
 
+/// Replication lag of the database, answered with 503 when it exceeds
+/// `MAX_REPLICATION_LAG_SECONDS` so that a lagging replica fails readiness.
+#[get("/health/replication")]
+async fn replication_health(
+    pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
+) -> Result<impl Responder, Error> {
+    let result = web::block(move || {
+        // Obtaining a connection from the pool is also a potentially blocking operation.
+        // So, it should be called within the `web::block` closure, as well.
+        let mut conn = pool.get(queries.into_inner())?;
+        conn.replication_status()
+    })
+    .await
+    .map_err(Error::BlockingError)?;
+
+    let status = result?;
+    let max_lag_seconds = *MAX_REPLICATION_LAG_SECONDS;
+    let lagging = status.in_recovery && status.lag_seconds.is_some_and(|lag| lag > max_lag_seconds);
+    let mut health = models::ReplicationHealth::new(
+        match (status.in_recovery, lagging) {
+            (false, _) => "not a replica",
+            (true, false) => "ok",
+            (true, true) => "lagging",
+        }
+        .to_string(),
+        status.in_recovery,
+        max_lag_seconds,
+    );
+    if status.in_recovery {
+        health.lag_seconds = status.lag_seconds;
+    }
+
+    let mut response = if lagging {
+        HttpResponse::ServiceUnavailable()
+    } else {
+        HttpResponse::Ok()
+    };
+    Ok(response
+        .content_type(mime::APPLICATION_JSON)
+        .message_body(serde_json::to_string(&health)?))
+}
+
+#[derive(Deserialize)]
+struct PageQuery {
+    limit: Option<i64>,
+    offset: Option<i64>,
+}
+
+#[get("/api/assessments")]
+async fn list_assessments(
+    request: HttpRequest,
+    query: web::Query<PageQuery>,
+    pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
+) -> Result<impl Responder, Error> {
+    let path = request.path().to_string();
+    validate_read_access(request)?;
+    let (limit, offset) = PAGE_SIZES
+        .page(query.limit, query.offset)
+        .map_err(Error::InvalidRequest)?;
+
+    let result = web::block(move || {
+        // Obtaining a connection from the pool is also a potentially blocking operation.
+        // So, it should be called within the `web::block` closure, as well.
+        let mut conn = pool.get(queries.into_inner())?;
+        Ok::<_, DatabaseError>((
+            conn.list_assessments(limit, offset)?,
+            conn.count_assessments()?,
+        ))
+    })
+    .await
+    .map_err(Error::BlockingError)?;
+
+    match result {
+        Ok((summaries, total)) => {
+            let mut response = HttpResponse::Ok();
+            response
+                .content_type(mime::APPLICATION_JSON)
+                .insert_header((TOTAL_COUNT_HEADER, total));
+            if let Some(links) = page_links(&path, limit, offset, total) {
+                response.insert_header((header::LINK, links));
+            }
+            Ok(response.message_body(serde_json::to_string(&summaries)?))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[derive(Deserialize)]
+struct ChangedQuery {
+    since: String,
+    limit: Option<i64>,
+    offset: Option<i64>,
+}
+
+#[get("/api/assessments/changed")]
+async fn changed_assessments(
+    request: HttpRequest,
+    query: web::Query<ChangedQuery>,
+    pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
+) -> Result<impl Responder, Error> {
+    validate_read_access(request)?;
+    let (since, (limit, offset)) = match (
+        parse_timestamp("since", &query.since),
+        PAGE_SIZES.page(query.limit, query.offset),
+    ) {
+        (Ok(since), Ok(page)) => (since, page),
+        (since, page) => {
+            let errors = since
+                .err()
+                .into_iter()
+                .chain(page.err().into_iter().flatten())
+                .collect();
+            return Err(Error::InvalidRequest(errors));
+        }
+    };
+
+    let result = web::block(move || {
+        // Obtaining a connection from the pool is also a potentially blocking operation.
+        // So, it should be called within the `web::block` closure, as well.
+        let mut conn = pool.get(queries.into_inner())?;
+        conn.changed_assessments(since, limit, offset)
+    })
+    .await
+    .map_err(Error::BlockingError)?;
+
+    match result {
+        Ok(changed) => Ok(HttpResponse::Ok()
+            .content_type(mime::APPLICATION_JSON)
+            .message_body(serde_json::to_string(&changed)?)),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[derive(Deserialize)]
+struct DimensionQuery {
+    dimension: Option<String>,
+}
+
 #[get("/api/assessments/{id}")]
 async fn assessment_graph(
+    request: HttpRequest,
     accept: web::Header<header::Accept>,
+    query: web::Query<DimensionQuery>,
     id: web::Path<String>,
     pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
 ) -> Result<impl Responder, Error> {
+    // Read before the access check consumes the request. An absent
+    // If-None-Match parses as an empty list, so it is looked up first.
+    let if_none_match = request
+        .headers()
+        .contains_key(header::IF_NONE_MATCH)
+        .then(|| header::IfNoneMatch::parse(&request).ok())
+        .flatten();
+    let if_modified_since = header::IfModifiedSince::parse(&request).ok();
+    validate_read_access(request)?;
     let uuid = parse_uuid(id.into_inner())?;
-    let accept_json_ld = accept
-        .0
-        .iter()
-        .any(|qi| qi.item.to_string() == "application/ld+json");
+    let media_type = negotiate(
+        &accept,
+        &[
+            TEXT_TURTLE,
+            APPLICATION_N_TRIPLES,
+            APPLICATION_RDF_XML,
+            APPLICATION_LD_JSON,
+        ],
+    )?;
+    let accept_json_ld = media_type == APPLICATION_LD_JSON;
+    let dimension = match query.into_inner().dimension {
+        Some(dimension) => Some(NamedNode::new(dimension).map_err(|e| {
+            Error::InvalidRequest(vec![FieldError::new(
+                "dimension".to_string(),
+                e.to_string(),
+            )])
+        })?),
+        None => None,
+    };
+
+    let (graph, updated_at) = web::block(move || {
+        // Obtaining a connection from the pool is also a potentially blocking operation.
+        // So, it should be called within the `web::block` closure, as well.
+        let mut conn = pool.get(queries.into_inner())?;
+        let (format, graph) = if accept_json_ld {
+            (graph::JSON_LD, conn.jsonld_assessment(uuid)?)
+        } else {
+            (RdfFormat::Turtle, conn.turtle_assessment(uuid)?)
+        };
+        let (mut graph, updated_at) = graph.ok_or(Error::NotFound(uuid))?;
+        if let Some(dimension) = dimension {
+            graph = graph::filter_dimension(format, &graph, dimension.as_ref())?;
+        }
+        if media_type == APPLICATION_N_TRIPLES {
+            // Sorted with canonical blank node labels, so that versions diff line by line.
+            graph = graph::canonical_nquads(RdfFormat::Turtle, &graph)?;
+            if !graph.is_empty() {
+                graph.push('\n');
+            }
+        } else if media_type == APPLICATION_RDF_XML {
+            graph = graph::rdf_xml(&graph)?;
+        }
+        Ok::<_, Error>((graph, updated_at))
+    })
+    .await
+    .map_err(Error::BlockingError)??;
+
+    // Hashed after filtering and conversion, so every representation has its own tag.
+    // Weak, as the Compress middleware may re-encode the body under the same tag.
+    let etag = header::EntityTag::new_weak(hex::encode(Sha256::digest(graph.as_bytes())));
+    // HTTP dates have whole seconds, so the stored timestamp is truncated to
+    // compare with those sent back by clients.
+    let last_modified = header::HttpDate::from(SystemTime::from(updated_at.trunc_subsecs(0)));
+    // If-Modified-Since is only considered without If-None-Match (RFC 9110, 13.2.2).
+    let not_modified = match (if_none_match, if_modified_since) {
+        (Some(if_none_match), _) => match if_none_match {
+            header::IfNoneMatch::Any => true,
+            header::IfNoneMatch::Items(tags) => tags.iter().any(|tag| tag.weak_eq(&etag)),
+        },
+        (None, Some(if_modified_since)) => last_modified <= if_modified_since.0,
+        (None, None) => false,
+    };
+    if not_modified {
+        return Ok(HttpResponse::NotModified()
+            .insert_header(header::ETag(etag))
+            .insert_header(header::LastModified(last_modified))
+            .insert_header((header::VARY, "Accept"))
+            .finish());
+    }
+
+    let mut response = HttpResponse::Ok();
+    response
+        .content_type(media_type)
+        .insert_header(header::ETag(etag))
+        .insert_header(header::LastModified(last_modified))
+        .insert_header((header::VARY, "Accept"));
+    Ok(signed(response, graph.into_bytes()))
+}
+
+#[derive(Deserialize)]
+struct GraphQuery {
+    version: Option<String>,
+}
+
+#[get("/api/assessments/{id}/graph")]
+async fn assessment_graph_version(
+    request: HttpRequest,
+    accept: web::Header<header::Accept>,
+    query: web::Query<GraphQuery>,
+    id: web::Path<String>,
+    pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
+) -> Result<impl Responder, Error> {
+    validate_read_access(request)?;
+    let uuid = parse_uuid(id.into_inner())?;
+    let version = match &query.version {
+        Some(version) => {
+            parse_timestamp("version", version).map_err(|e| Error::InvalidRequest(vec![e]))?
+        }
+        None => Utc::now(),
+    };
+    let media_type = negotiate(&accept, &[TEXT_TURTLE, APPLICATION_LD_JSON])?;
+    let accept_json_ld = media_type == APPLICATION_LD_JSON;
+
+    let result = web::block(move || {
+        // Obtaining a connection from the pool is also a potentially blocking operation.
+        // So, it should be called within the `web::block` closure, as well.
+        let mut conn = pool.get(queries.into_inner())?;
+        conn.graph_version(uuid, version, accept_json_ld)?
+            .ok_or(Error::NotFound(uuid))
+    })
+    .await
+    .map_err(Error::BlockingError)?;
+
+    match result {
+        Ok(graph) => Ok(HttpResponse::Ok()
+            .content_type(media_type)
+            .message_body(graph)),
+        Err(e) => Err(e),
+    }
+}
+
+#[get("/api/assessments/{id}/dimensions/{dimension_id}/trend")]
+async fn dimension_trend(
+    request: HttpRequest,
+    path: web::Path<(String, String)>,
+    pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
+) -> Result<impl Responder, Error> {
+    validate_read_access(request)?;
+    let (id, dimension_id) = path.into_inner();
+    let uuid = parse_uuid(id)?;
+
+    let result = web::block(move || {
+        // Obtaining a connection from the pool is also a potentially blocking operation.
+        // So, it should be called within the `web::block` closure, as well.
+        let mut conn = pool.get(queries.into_inner())?;
+        conn.dimension_trend(uuid, &dimension_id)
+    })
+    .await
+    .map_err(Error::BlockingError)?;
+
+    match result {
+        Ok(points) => Ok(HttpResponse::Ok()
+            .content_type(mime::APPLICATION_JSON)
+            .message_body(serde_json::to_string(&points)?)),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[get("/api/assessments/{id}/distributions")]
+async fn assessment_distributions(
+    request: HttpRequest,
+    id: web::Path<String>,
+    pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
+) -> Result<impl Responder, Error> {
+    validate_read_access(request)?;
+    let uuid = parse_uuid(id.into_inner())?;
+
+    let result = web::block(move || {
+        // Obtaining a connection from the pool is also a potentially blocking operation.
+        // So, it should be called within the `web::block` closure, as well.
+        let mut conn = pool.get(queries.into_inner())?;
+        conn.json_score(uuid)?.ok_or(Error::NotFound(uuid))
+    })
+    .await
+    .map_err(Error::BlockingError)?;
+
+    let mut score = result?;
+    aggregation::fill_ratios(&mut score, *OVERALL_STRATEGY);
+    Ok(HttpResponse::Ok()
+        .content_type(mime::APPLICATION_JSON)
+        .message_body(serde_json::to_string(&score.distributions)?))
+}
+
+#[get("/api/assessments/{id}/score")]
+async fn assessment_score(
+    request: HttpRequest,
+    id: web::Path<String>,
+    pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
+) -> Result<impl Responder, Error> {
+    validate_read_access(request)?;
+    let uuid = parse_uuid(id.into_inner())?;
+
+    let result = web::block(move || {
+        // Obtaining a connection from the pool is also a potentially blocking operation.
+        // So, it should be called within the `web::block` closure, as well.
+        let mut conn = pool.get(queries.into_inner())?;
+        conn.json_score(uuid)?.ok_or(Error::NotFound(uuid))
+    })
+    .await
+    .map_err(Error::BlockingError)?;
+
+    let mut score = result?;
+    aggregation::fill_ratios(&mut score, *OVERALL_STRATEGY);
+    Ok(HttpResponse::Ok()
+        .content_type(mime::APPLICATION_JSON)
+        .message_body(serde_json::to_string(&score)?))
+}
+
+#[get("/api/assessments/{id}/uri")]
+async fn assessment_uri(
+    request: HttpRequest,
+    id: web::Path<String>,
+    pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
+) -> Result<impl Responder, Error> {
+    validate_read_access(request)?;
+    let uuid = parse_uuid(id.into_inner())?;
+
+    let result = web::block(move || {
+        // Obtaining a connection from the pool is also a potentially blocking operation.
+        // So, it should be called within the `web::block` closure, as well.
+        let mut conn = pool.get(queries.into_inner())?;
+        conn.assessment_uri(uuid)?.ok_or(Error::NotFound(uuid))
+    })
+    .await
+    .map_err(Error::BlockingError)?;
+
+    let (dataset_uri, assessment_count) = result?;
+    Ok(HttpResponse::Ok()
+        .content_type(mime::APPLICATION_JSON)
+        .message_body(serde_json::to_string(&models::AssessmentUri {
+            id: uuid.to_string(),
+            dataset_uri,
+            assessment_count,
+            duplicate_uri: assessment_count > 1,
+        })?))
+}
+
+#[get("/api/assessments/{id}/annotations")]
+async fn annotations(
+    request: HttpRequest,
+    id: web::Path<String>,
+    pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
+) -> Result<impl Responder, Error> {
+    validate_read_access(request)?;
+    let uuid = parse_uuid(id.into_inner())?;
+
+    let result = web::block(move || {
+        // Obtaining a connection from the pool is also a potentially blocking operation.
+        // So, it should be called within the `web::block` closure, as well.
+        let mut conn = pool.get(queries.into_inner())?;
+        conn.annotations(uuid)?.ok_or(Error::NotFound(uuid))
+    })
+    .await
+    .map_err(Error::BlockingError)?;
+
+    let annotations: Vec<models::Annotation> = result?
+        .into_iter()
+        .map(|(key, value)| models::Annotation::new(key, value))
+        .collect();
+    Ok(HttpResponse::Ok()
+        .content_type(mime::APPLICATION_JSON)
+        .message_body(serde_json::to_string(&annotations)?))
+}
+
+/// Sets an annotation of an assessment. Annotations are kept apart from the
+/// scores, so posting the assessment again leaves them in place.
+#[post("/api/assessments/{id}/annotations")]
+async fn set_annotation(
+    request: HttpRequest,
+    body: web::Bytes,
+    id: web::Path<String>,
+    pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
+    write_freeze: web::Data<WriteFreeze>,
+) -> Result<impl Responder, Error> {
+    validate_api_key(request)?;
+    write_freeze.check()?;
+    let uuid = parse_uuid(id.into_inner())?;
+    let annotation: models::Annotation = serde_json::from_str(from_utf8(&body)?)?;
+    if annotation.key.is_empty() {
+        return Err(Error::InvalidRequest(vec![FieldError::new(
+            "key".to_string(),
+            "must not be empty".to_string(),
+        )]));
+    }
 
     let result = web::block(move || {
         // Obtaining a connection from the pool is also a potentially blocking operation.
         // So, it should be called within the `web::block` closure, as well.
-        let mut conn = pool.get()?;
-        if accept_json_ld {
-            conn.jsonld_assessment(uuid)?.ok_or(Error::NotFound(uuid))
+        let mut conn = pool.get(queries.into_inner())?;
+        if conn.set_annotation(uuid, &annotation.key, &annotation.value)? {
+            Ok(annotation)
         } else {
-            conn.turtle_assessment(uuid)?.ok_or(Error::NotFound(uuid))
+            Err(Error::NotFound(uuid))
         }
     })
     .await
-    .map_err(|e| Error::BlockingError(e.into()))?;
+    .map_err(Error::BlockingError)?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(mime::APPLICATION_JSON)
+        .message_body(serde_json::to_string(&result?)?))
+}
+
+/// Removes an annotation of an assessment, answering 204 whether or not it
+/// was set.
+#[delete("/api/assessments/{id}/annotations/{key}")]
+async fn delete_annotation(
+    request: HttpRequest,
+    path: web::Path<(String, String)>,
+    pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
+    write_freeze: web::Data<WriteFreeze>,
+) -> Result<impl Responder, Error> {
+    validate_api_key(request)?;
+    write_freeze.check()?;
+    let (id, key) = path.into_inner();
+    let uuid = parse_uuid(id)?;
+
+    let result = web::block(move || {
+        // Obtaining a connection from the pool is also a potentially blocking operation.
+        // So, it should be called within the `web::block` closure, as well.
+        let mut conn = pool.get(queries.into_inner())?;
+        conn.delete_annotation(uuid, &key)
+    })
+    .await
+    .map_err(Error::BlockingError)?;
+
+    result?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[post("/api/assessments/{id}/sparql-update")]
+async fn sparql_update(
+    request: HttpRequest,
+    body: web::Bytes,
+    id: web::Path<String>,
+    pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
+    write_freeze: web::Data<WriteFreeze>,
+) -> Result<impl Responder, Error> {
+    validate_api_key(request)?;
+    write_freeze.check()?;
+    let uuid = parse_uuid(id.into_inner())?;
+    let update = from_utf8(&body)?.to_string();
+
+    let result = web::block(move || {
+        // Obtaining a connection from the pool is also a potentially blocking operation.
+        // So, it should be called within the `web::block` closure, as well.
+        let mut conn = pool.get(queries.into_inner())?;
+        let (turtle, _) = conn.turtle_assessment(uuid)?.ok_or(Error::NotFound(uuid))?;
+        let (turtle, jsonld) = graph::apply_update(&turtle, &update)?;
+        validate_graph_size("turtle_assessment", &turtle)?;
+        validate_graph_size("jsonld_assessment", &jsonld)?;
+        if conn.update_graphs(uuid, &turtle, &jsonld, *GRAPH_STORAGE)? {
+            Ok(turtle)
+        } else {
+            Err(Error::NotFound(uuid))
+        }
+    })
+    .await
+    .map_err(Error::BlockingError)?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/turtle")
+        .message_body(result?))
+}
+
+#[derive(Deserialize)]
+struct UpdateQuery {
+    #[serde(rename = "return")]
+    return_: Option<String>,
+}
+
+/// A posted assessment that passed validation, with its graphs parsed.
+struct ValidatedUpdate {
+    update: models::ScorePostRequest,
+    publisher_uri: Option<String>,
+    canonical_graphs: [String; 2],
+    warnings: Vec<FieldError>,
+}
+
+/// Runs every check of a posted assessment that needs no database, parsing
+/// its graphs on the blocking pool.
+async fn validate_update(body: serde_json::Value) -> Result<ValidatedUpdate, Error> {
+    validate_score_ranges(&body)?;
+    let mut update: models::ScorePostRequest = serde_json::from_value(body)?;
+    validate_graph_size("turtle_assessment", &update.turtle_assessment)?;
+    validate_graph_size("jsonld_assessment", &update.jsonld_assessment)?;
+    validate_dimension_scores(&update.scores)?;
+    let warnings = unknown_dimension_warnings(&update.scores);
+    dedup_dimensions(
+        &mut update.scores.dataset.dimensions,
+        *DUPLICATE_DIMENSION_POLICY,
+    )?;
+    let (publisher_uri, canonical_graphs) = {
+        let turtle = update.turtle_assessment.clone();
+        let jsonld = update.jsonld_assessment.clone();
+        let dataset_score = update.scores.clone();
+        let publisher_uri = update.publisher_uri.clone();
+        parse_with_timeout(*GRAPH_PARSE_TIMEOUT, move || {
+            // Stored graphs are served as they are, so both have to parse.
+            for (format, field, graph) in [
+                (RdfFormat::Turtle, "turtle_assessment", &turtle),
+                (graph::JSON_LD, "jsonld_assessment", &jsonld),
+            ] {
+                if let Some(e) = graph::parse_error(format, graph) {
+                    return Err(Error::InvalidGraph(format!("{field}: {e}")));
+                }
+            }
+            if *VALIDATE_DIMENSION_IDS {
+                graph::validate_dimension_ids(&turtle, &dataset_score)?;
+            }
+            if *VERIFY_DIMENSION_SCORES {
+                graph::verify_dimension_scores(&turtle, &dataset_score)?;
+            }
+            let dataset_uri = &dataset_score.dataset.id;
+            let publisher_uri = match publisher_uri {
+                Some(publisher_uri) => Some(publisher_uri),
+                None => graph::publisher(&turtle, dataset_uri)?,
+            };
+            let canonical_graphs = [
+                graph::canonical_nquads(RdfFormat::Turtle, &turtle)?,
+                graph::canonical_nquads(graph::JSON_LD, &jsonld)?,
+            ];
+            Ok((publisher_uri, canonical_graphs))
+        })
+        .await?
+    };
+
+    Ok(ValidatedUpdate {
+        update,
+        publisher_uri,
+        canonical_graphs,
+        warnings,
+    })
+}
+
+impl ValidatedUpdate {
+    /// The assessment row to store under `uuid`, with its content hash, and
+    /// the rows of its dataset dimensions.
+    fn rows(&self, uuid: Uuid) -> Result<(DatasetAssessment, Vec<Dimension>), serde_json::Error> {
+        let update = &self.update;
+        let dataset_uri = &update.scores.dataset.id;
+        let mut assessment = DatasetAssessment {
+            id: uuid.to_string(),
+            dataset_uri: dataset_uri.clone(),
+            turtle_assessment: Some(update.turtle_assessment.clone()),
+            jsonld_assessment: Some(update.jsonld_assessment.clone()),
+            json_score: stored_json_score(&update.scores)?,
+            catalog_uri: update.catalog_uri.clone(),
+            publisher_uri: self.publisher_uri.clone(),
+            content_hash: None,
+            distribution_count: update.scores.distributions.len() as i32,
+        };
+        assessment.content_hash = Some(content_hash(&self.canonical_graphs, &assessment));
+
+        let dimensions = update
+            .scores
+            .dataset
+            .dimensions
+            .iter()
+            .map(|dimension| Dimension {
+                dataset_uri: dataset_uri.clone(),
+                id: dimension.id.clone(),
+                score: dimension.score,
+                max_score: dimension.max_score,
+            })
+            .collect();
+        Ok((assessment, dimensions))
+    }
+}
+
+#[post("/api/assessments/{id}")]
+async fn update_assessment(
+    request: HttpRequest,
+    query: web::Query<UpdateQuery>,
+    body: web::Bytes,
+    id: web::Path<String>,
+    pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
+    write_freeze: web::Data<WriteFreeze>,
+) -> Result<impl Responder, Error> {
+    validate_api_key(request)?;
+    write_freeze.check()?;
+    let uuid = parse_uuid(id.into_inner())?;
+    let return_diff = match query.return_.as_deref() {
+        None => false,
+        Some("diff") => true,
+        Some(_) => {
+            return Err(Error::InvalidRequest(vec![FieldError::new(
+                "return".to_string(),
+                "expected diff".to_string(),
+            )]))
+        }
+    };
+    let body: serde_json::Value = serde_json::from_str(from_utf8(&body)?)?;
+    let mut validated = validate_update(body).await?;
+    let warnings = std::mem::take(&mut validated.warnings);
+
+    let result: Result<(Option<Vec<models::DimensionDiff>>, bool), DatabaseError> =
+        web::block(move || {
+            // Obtaining a connection from the pool is also a potentially blocking operation.
+            // So, it should be called within the `web::block` closure, as well.
+            let mut conn = pool.get(queries.into_inner())?;
+
+            let diff = if return_diff {
+                let previous = conn.json_score(uuid)?;
+                Some(diff::dimension_diff(
+                    previous.as_ref(),
+                    &validated.update.scores,
+                ))
+            } else {
+                None
+            };
+
+            let (assessment, dimensions) = validated.rows(uuid)?;
+            if conn.content_hash(uuid)? == assessment.content_hash {
+                return Ok((diff, false));
+            }
+            conn.store_assessment_with_dimensions(assessment, &dimensions, *GRAPH_STORAGE)?;
+
+            Ok((diff, true))
+        })
+        .await
+        .map_err(Error::BlockingError)?;
+
+    match result {
+        Ok((diff, stored)) => {
+            let mut response = if stored {
+                HttpResponse::Accepted()
+            } else {
+                let mut response = HttpResponse::Ok();
+                response.insert_header((NOT_MODIFIED_HEADER, "true"));
+                response
+            };
+            let body = models::ScorePostResponse {
+                dimensions: diff,
+                warnings: (!warnings.is_empty()).then_some(warnings),
+            };
+            Ok(response.content_type(mime::APPLICATION_JSON).message_body(
+                if body == models::ScorePostResponse::new() {
+                    "".to_string()
+                } else {
+                    serde_json::to_string(&body)?
+                },
+            ))
+        }
+        Err(e) if e.is_dataset_uri_conflict() => Err(dataset_conflict()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// A dataset has one assessment, so posting its uri under another id is refused.
+fn dataset_conflict() -> Error {
+    Error::DatasetConflict(vec![FieldError::new(
+        "scores.dataset.id".to_string(),
+        "is already assessed under another FDK ID".to_string(),
+    )])
+}
+
+/// Outcome of a bulk item that was not stored, with the status and error a
+/// post of the item on its own would have been answered with.
+fn failed_item(id: String, e: &Error) -> models::BulkScoreResult {
+    let mut result = models::BulkScoreResult::new(id, e.error_response().status().as_u16() as i32);
+    result.error = Some(e.to_string());
+    result.errors = e.field_errors().map(<[FieldError]>::to_vec);
+    result
+}
+
+/// Assessments posted in one request, each validated like a post to
+/// `/api/assessments/{id}`. The valid ones are stored in a single transaction,
+/// and the outcome of every item is reported in the order they were posted.
+#[post("/api/assessments/bulk")]
+async fn bulk_update_assessments(
+    request: HttpRequest,
+    body: web::Bytes,
+    pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
+    write_freeze: web::Data<WriteFreeze>,
+) -> Result<impl Responder, Error> {
+    validate_api_key(request)?;
+    write_freeze.check()?;
+    let items: Vec<serde_json::Value> = serde_json::from_str(from_utf8(&body)?)?;
+
+    let mut results = Vec::with_capacity(items.len());
+    let mut valid = Vec::new();
+    let mut ids = HashSet::new();
+    let mut dataset_uris = HashSet::new();
+    for mut item in items {
+        let id = match item.as_object_mut().and_then(|fields| fields.remove("id")) {
+            Some(serde_json::Value::String(id)) => id,
+            _ => String::new(),
+        };
+        let validated = match parse_uuid(id.clone()) {
+            Ok(uuid) if !ids.insert(uuid) => Err(Error::InvalidRequest(vec![FieldError::new(
+                "id".to_string(),
+                "appears more than once in the request".to_string(),
+            )])),
+            Ok(uuid) => validate_update(item).await.and_then(|validated| {
+                // Datasets have one assessment, so a second one would fail the transaction.
+                if dataset_uris.insert(validated.update.scores.dataset.id.clone()) {
+                    Ok((uuid, validated))
+                } else {
+                    Err(Error::InvalidRequest(vec![FieldError::new(
+                        "scores.dataset.id".to_string(),
+                        "appears more than once in the request".to_string(),
+                    )]))
+                }
+            }),
+            Err(e) => Err(e),
+        };
+        match validated {
+            Ok((uuid, mut validated)) => {
+                let mut result = models::BulkScoreResult::new(id, 0);
+                let warnings = std::mem::take(&mut validated.warnings);
+                result.warnings = (!warnings.is_empty()).then_some(warnings);
+                valid.push((results.len(), uuid, validated));
+                results.push(result);
+            }
+            Err(e) => results.push(failed_item(id, &e)),
+        }
+    }
+
+    let stored = web::block(move || {
+        // Obtaining a connection from the pool is also a potentially blocking operation.
+        // So, it should be called within the `web::block` closure, as well.
+        let mut conn = pool.get(queries.into_inner())?;
+
+        let uuids: Vec<Uuid> = valid.iter().map(|(_, uuid, _)| *uuid).collect();
+        let hashes = conn.content_hashes(&uuids)?;
+        let dataset_uris: Vec<String> = valid
+            .iter()
+            .map(|(_, _, validated)| validated.update.scores.dataset.id.clone())
+            .collect();
+        let stored_ids = conn.assessment_ids_by_uri(&dataset_uris)?;
+        let mut stored = Vec::with_capacity(valid.len());
+        let mut to_store = Vec::new();
+        let mut dimensions = Vec::new();
+        for (position, uuid, validated) in &valid {
+            let (assessment, rows) = validated.rows(*uuid)?;
+            // Stored under another id, the dataset would fail the whole transaction.
+            if stored_ids
+                .get(&assessment.dataset_uri)
+                .is_some_and(|id| *id != assessment.id)
+            {
+                stored.push((*position, None));
+                continue;
+            }
+            let changed = hashes.get(&assessment.id) != assessment.content_hash.as_ref();
+            if changed {
+                to_store.push(assessment);
+                dimensions.extend(rows);
+            }
+            stored.push((*position, Some(changed)));
+        }
+        if !to_store.is_empty() {
+            conn.store_assessments_with_dimensions(to_store, &dimensions, *GRAPH_STORAGE)?;
+        }
+        Ok::<_, DatabaseError>(stored)
+    })
+    .await
+    .map_err(Error::BlockingError)?
+    .map_err(|e| {
+        if e.is_dataset_uri_conflict() {
+            dataset_conflict()
+        } else {
+            e.into()
+        }
+    })?;
+
+    for (position, changed) in stored {
+        let status = match changed {
+            Some(true) => StatusCode::ACCEPTED,
+            Some(false) => StatusCode::OK,
+            None => {
+                let id = std::mem::take(&mut results[position].id);
+                results[position] = failed_item(id, &dataset_conflict());
+                continue;
+            }
+        };
+        results[position].status = status.as_u16() as i32;
+    }
+    Ok(HttpResponse::Ok()
+        .content_type(mime::APPLICATION_JSON)
+        .message_body(serde_json::to_string(&results)?))
+}
+
+/// The posted scores as stored, without the ratios. Those follow from the raw
+/// scores and `OVERALL_STRATEGY`, so they are filled in when scores are served.
+fn stored_json_score(score: &models::DatasetScore) -> Result<String, serde_json::Error> {
+    fn remove_ratios(score: &mut serde_json::Value) {
+        let Some(score) = score.as_object_mut() else {
+            return;
+        };
+        score.remove("ratio");
+        if let Some(dimensions) = score.get_mut("dimensions").and_then(|d| d.as_array_mut()) {
+            for dimension in dimensions.iter_mut().filter_map(|d| d.as_object_mut()) {
+                dimension.remove("ratio");
+            }
+        }
+    }
+
+    let mut json = serde_json::to_value(score)?;
+    remove_ratios(&mut json["dataset"]);
+    if let Some(distributions) = json["distributions"].as_array_mut() {
+        distributions.iter_mut().for_each(remove_ratios);
+    }
+    serde_json::to_string(&json)
+}
+
+/// Hex SHA-256 over the canonical N-Quads of both graphs and the rest of the
+/// stored content, equal for two posts that differ at most in how their graphs
+/// are serialized.
+fn content_hash(canonical_graphs: &[String; 2], assessment: &DatasetAssessment) -> String {
+    let mut hasher = Sha256::new();
+    for part in [
+        Some(canonical_graphs[0].as_str()),
+        Some(canonical_graphs[1].as_str()),
+        Some(assessment.json_score.as_str()),
+        assessment.catalog_uri.as_deref(),
+        assessment.publisher_uri.as_deref(),
+    ] {
+        // Length prefixed, so content cannot shift between parts.
+        match part {
+            Some(part) => {
+                hasher.update((part.len() as u64).to_be_bytes());
+                hasher.update(part);
+            }
+            None => hasher.update(u64::MAX.to_be_bytes()),
+        }
+    }
+    hex::encode(hasher.finalize())
+}
+
+#[post("/api/admin/warm-aggregates")]
+async fn warm_aggregates(
+    request: HttpRequest,
+    pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
+    write_freeze: web::Data<WriteFreeze>,
+) -> Result<impl Responder, Error> {
+    validate_api_key(request)?;
+    write_freeze.check()?;
+
+    let result = web::block(move || {
+        // Obtaining a connection from the pool is also a potentially blocking operation.
+        // So, it should be called within the `web::block` closure, as well.
+        let mut conn = pool.get(queries.into_inner())?;
+        conn.warm_aggregates()
+    })
+    .await
+    .map_err(Error::BlockingError)?;
+
+    match result {
+        Ok(groups) => Ok(HttpResponse::Ok()
+            .content_type(mime::APPLICATION_JSON)
+            .message_body(serde_json::to_string(&models::AggregateWarmup::new(
+                groups,
+            ))?)),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// JSON Schema of the assessment payload accepted by `update_assessment`,
+/// generated from the models so it follows them as they change.
+#[get("/api/schema/score")]
+async fn score_schema() -> Result<impl Responder, Error> {
+    Ok(HttpResponse::Ok()
+        .content_type(mime::APPLICATION_JSON)
+        .message_body(serde_json::to_string(&schemars::schema_for!(
+            models::ScorePostRequest
+        ))?))
+}
+
+/// The spec served as JSON on `/openapi.json`, rendered as YAML once at startup.
+struct OpenApiYaml(String);
+
+#[get("/openapi.yaml")]
+async fn openapi_yaml(yaml: web::Data<OpenApiYaml>) -> impl Responder {
+    HttpResponse::Ok()
+        .content_type("application/yaml")
+        .body(yaml.0.clone())
+}
+
+#[get("/metrics")]
+async fn metrics(
+    gauges: web::Data<DimensionGauges>,
+    requests: Option<web::Data<RequestMetrics>>,
+) -> impl Responder {
+    let mut text = gauges.render();
+    if let Some(requests) = requests {
+        text.push_str(&requests.render());
+    }
+    HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4")
+        .body(text)
+}
+
+/// Recomputes the store-wide dimension aggregates behind the `/metrics` gauges.
+async fn refresh_gauges(pool: PgPool, gauges: &DimensionGauges) -> Result<(), Error> {
+    let overview = web::block(move || {
+        let mut conn = pool.get(QueryCount::default())?;
+        conn.dimensions_overview()
+    })
+    .await
+    .map_err(Error::BlockingError)??;
+    gauges.set(overview);
+    Ok(())
+}
+
+#[get("/api/dimensions/overview")]
+async fn dimensions_overview(
+    request: HttpRequest,
+    pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
+) -> Result<impl Responder, Error> {
+    validate_read_access(request)?;
+    let result = web::block(move || {
+        // Obtaining a connection from the pool is also a potentially blocking operation.
+        // So, it should be called within the `web::block` closure, as well.
+        let mut conn = pool.get(queries.into_inner())?;
+        conn.dimensions_overview()
+    })
+    .await
+    .map_err(Error::BlockingError)?;
+
+    match result {
+        Ok(overview) => Ok(HttpResponse::Ok()
+            .content_type(mime::APPLICATION_JSON)
+            .message_body(serde_json::to_string(&overview)?)),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[post("/api/admin/validate-graphs")]
+async fn validate_graphs(
+    request: HttpRequest,
+    pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
+) -> Result<impl Responder, Error> {
+    validate_api_key(request)?;
+
+    let result = web::block(move || {
+        // Obtaining a connection from the pool is also a potentially blocking operation.
+        // So, it should be called within the `web::block` closure, as well.
+        let mut conn = pool.get(queries.into_inner())?;
+
+        let mut report = models::GraphValidationReport::default();
+        conn.for_each_graph(|id, turtle, jsonld| {
+            report.checked += 1;
+            for (format, name, graph) in [
+                (RdfFormat::Turtle, "turtle", turtle),
+                (graph::JSON_LD, "jsonld", jsonld),
+            ] {
+                if let Some(error) = graph::parse_error(format, &graph) {
+                    report.invalid.push(models::InvalidGraph {
+                        id: id.clone(),
+                        format: name.to_string(),
+                        error,
+                    });
+                }
+            }
+        })?;
+
+        Ok::<_, DatabaseError>(report)
+    })
+    .await
+    .map_err(Error::BlockingError)?;
+
+    match result {
+        Ok(report) => Ok(HttpResponse::Ok()
+            .content_type(mime::APPLICATION_JSON)
+            .message_body(serde_json::to_string(&report)?)),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[post("/api/admin/prune-orphans")]
+async fn prune_orphans(
+    request: HttpRequest,
+    pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
+    write_freeze: web::Data<WriteFreeze>,
+) -> Result<impl Responder, Error> {
+    validate_api_key(request)?;
+    write_freeze.check()?;
+
+    let result = web::block(move || {
+        // Obtaining a connection from the pool is also a potentially blocking operation.
+        // So, it should be called within the `web::block` closure, as well.
+        let mut conn = pool.get(queries.into_inner())?;
+        conn.prune_orphans()
+    })
+    .await
+    .map_err(Error::BlockingError)?;
+
+    match result {
+        Ok(pruned) => {
+            tracing::info!(dimensions = pruned, "orphaned dimensions pruned");
+            Ok(HttpResponse::Ok()
+                .content_type(mime::APPLICATION_JSON)
+                .message_body(serde_json::to_string(&models::PrunedOrphans::new(
+                    pruned as i64,
+                ))?))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[post("/api/admin/reset")]
+async fn reset(
+    request: HttpRequest,
+    pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
+    write_freeze: web::Data<WriteFreeze>,
+) -> Result<impl Responder, Error> {
+    validate_api_key(request)?;
+    ensure_not_production(&ENVIRONMENT)?;
+    write_freeze.check()?;
+
+    let result = web::block(move || {
+        // Obtaining a connection from the pool is also a potentially blocking operation.
+        // So, it should be called within the `web::block` closure, as well.
+        let mut conn = pool.get(queries.into_inner())?;
+        conn.reset()
+    })
+    .await
+    .map_err(Error::BlockingError)?;
+
+    match result {
+        Ok((removed_assessments, removed_dimensions)) => {
+            tracing::warn!(
+                assessments = removed_assessments,
+                dimensions = removed_dimensions,
+                "all assessments removed"
+            );
+            Ok(HttpResponse::Ok()
+                .content_type(mime::APPLICATION_JSON)
+                .message_body(serde_json::to_string(&models::ResetCounts::new(
+                    removed_assessments as i64,
+                    removed_dimensions as i64,
+                ))?))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[post("/api/admin/write-freeze")]
+async fn set_write_freeze(
+    request: HttpRequest,
+    body: web::Bytes,
+    write_freeze: web::Data<WriteFreeze>,
+) -> Result<impl Responder, Error> {
+    validate_api_key(request)?;
+    let setting: models::WriteFreezeSetting = serde_json::from_str(from_utf8(&body)?)?;
+    let until = setting
+        .until
+        .map(|until| parse_timestamp("until", &until))
+        .transpose()
+        .map_err(|e| Error::InvalidRequest(vec![e]))?;
+
+    write_freeze.set(until);
+
+    Ok(HttpResponse::Ok()
+        .content_type(mime::APPLICATION_JSON)
+        .message_body(serde_json::to_string(&models::WriteFreezeSetting {
+            until: until.map(|until| until.to_rfc3339()),
+        })?))
+}
+
+#[get("/api/admin/recent-errors")]
+async fn list_recent_errors(
+    request: HttpRequest,
+    recent_errors: web::Data<RecentErrors>,
+) -> Result<impl Responder, Error> {
+    validate_api_key(request)?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(mime::APPLICATION_JSON)
+        .message_body(serde_json::to_string(&recent_errors.list())?))
+}
+
+#[get("/api/debug/pool")]
+async fn pool_state(
+    request: HttpRequest,
+    pool: web::Data<PgPool>,
+) -> Result<impl Responder, Error> {
+    validate_api_key(request)?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(mime::APPLICATION_JSON)
+        .message_body(serde_json::to_string(&pool.state())?))
+}
+
+#[get("/api/catalogs/counts")]
+async fn catalog_counts(
+    request: HttpRequest,
+    pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
+) -> Result<impl Responder, Error> {
+    validate_read_access(request)?;
+    let result = web::block(move || {
+        // Obtaining a connection from the pool is also a potentially blocking operation.
+        // So, it should be called within the `web::block` closure, as well.
+        let mut conn = pool.get(queries.into_inner())?;
+        conn.catalog_counts()
+    })
+    .await
+    .map_err(Error::BlockingError)?;
+
+    match result {
+        Ok(counts) => Ok(HttpResponse::Ok()
+            .content_type(mime::APPLICATION_JSON)
+            .message_body(serde_json::to_string(&counts)?)),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[derive(Deserialize)]
+struct CompareQuery {
+    a: Option<String>,
+    b: Option<String>,
+}
+
+#[get("/api/catalogs/compare")]
+async fn compare_catalogs(
+    request: HttpRequest,
+    query: web::Query<CompareQuery>,
+    pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
+) -> Result<impl Responder, Error> {
+    validate_read_access(request)?;
+    let CompareQuery { a, b } = query.into_inner();
+    let mut errors = Vec::new();
+    for (field, uri) in [("a", &a), ("b", &b)] {
+        match uri {
+            None => errors.push(FieldError::new(
+                field.to_string(),
+                "catalog uri is required".to_string(),
+            )),
+            Some(uri) => {
+                if let Err(e) = uri.parse::<Uri>() {
+                    errors.push(FieldError::new(field.to_string(), e.to_string()));
+                }
+            }
+        }
+    }
+    let (Some(a), Some(b), true) = (a, b, errors.is_empty()) else {
+        return Err(Error::InvalidRequest(errors));
+    };
+
+    let result = web::block(move || {
+        // Obtaining a connection from the pool is also a potentially blocking operation.
+        // So, it should be called within the `web::block` closure, as well.
+        let mut conn = pool.get(queries.into_inner())?;
+        let dimensions = conn.compare_catalogs(&a, &b)?;
+        Ok::<_, DatabaseError>(models::CatalogComparison { a, b, dimensions })
+    })
+    .await
+    .map_err(Error::BlockingError)?;
+
+    match result {
+        Ok(comparison) => Ok(HttpResponse::Ok()
+            .content_type(mime::APPLICATION_JSON)
+            .message_body(serde_json::to_string(&comparison)?)),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[derive(Deserialize)]
+struct VsAverageQuery {
+    dataset: Option<String>,
+}
+
+#[get("/api/scores/vs-average")]
+async fn vs_average(
+    request: HttpRequest,
+    query: web::Query<VsAverageQuery>,
+    pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
+) -> Result<impl Responder, Error> {
+    validate_read_access(request)?;
+    let dataset = match query.into_inner().dataset {
+        None => Err("dataset uri is required".to_string()),
+        Some(dataset) => match dataset.parse::<Uri>() {
+            Ok(_) => Ok(dataset),
+            Err(e) => Err(e.to_string()),
+        },
+    }
+    .map_err(|e| Error::InvalidRequest(vec![FieldError::new("dataset".to_string(), e)]))?;
+
+    let result = web::block(move || {
+        // Obtaining a connection from the pool is also a potentially blocking operation.
+        // So, it should be called within the `web::block` closure, as well.
+        let mut conn = pool.get(queries.into_inner())?;
+        let dimensions = conn.dataset_vs_average(&dataset)?;
+        Ok::<_, DatabaseError>(models::DatasetVsAverage {
+            dataset,
+            dimensions,
+        })
+    })
+    .await
+    .map_err(Error::BlockingError)?;
+
+    match result {
+        Ok(comparison) => Ok(HttpResponse::Ok()
+            .content_type(mime::APPLICATION_JSON)
+            .message_body(serde_json::to_string(&comparison)?)),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[get("/api/publishers/{uri}/aggregate")]
+async fn publisher_aggregate(
+    request: HttpRequest,
+    uri: web::Path<String>,
+    pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
+) -> Result<impl Responder, Error> {
+    validate_read_access(request)?;
+    let publisher_uri = uri.into_inner();
+    if let Err(e) = publisher_uri.parse::<Uri>() {
+        return Err(Error::InvalidRequest(vec![FieldError::new(
+            "uri".to_string(),
+            e.to_string(),
+        )]));
+    }
+
+    let result = web::block(move || {
+        // Obtaining a connection from the pool is also a potentially blocking operation.
+        // So, it should be called within the `web::block` closure, as well.
+        let mut conn = pool.get(queries.into_inner())?;
+        let aggregations = conn.publisher_aggregates(&publisher_uri)?;
+        Ok::<_, DatabaseError>(models::PublisherAggregate {
+            publisher_uri,
+            aggregations,
+        })
+    })
+    .await
+    .map_err(Error::BlockingError)?;
+
+    match result {
+        Ok(aggregate) => Ok(HttpResponse::Ok()
+            .content_type(mime::APPLICATION_JSON)
+            .message_body(serde_json::to_string(&aggregate)?)),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[derive(Deserialize)]
+struct ScoresQuery {
+    as_percent: Option<bool>,
+    pivot: Option<String>,
+    source: Option<String>,
+    stats: Option<String>,
+    weight_by: Option<String>,
+}
+
+#[post("/api/scores")]
+async fn scores(
+    request: HttpRequest,
+    accept: web::Header<header::Accept>,
+    query: web::Query<ScoresQuery>,
+    pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
+    body: web::Bytes,
+) -> Result<impl Responder, Error> {
+    validate_read_access(request)?;
+    let accept_protobuf = accept
+        .0
+        .iter()
+        .any(|qi| qi.item == "application/x-protobuf");
+    let pivot = match query.pivot.as_deref() {
+        None => false,
+        Some("dimension") if query.as_percent.unwrap_or(false) => {
+            return Err(Error::InvalidRequest(vec![FieldError::new(
+                "pivot".to_string(),
+                "cannot be combined with as_percent".to_string(),
+            )]))
+        }
+        Some("dimension") => true,
+        Some(_) => {
+            return Err(Error::InvalidRequest(vec![FieldError::new(
+                "pivot".to_string(),
+                "expected dimension".to_string(),
+            )]))
+        }
+    };
+    if accept_protobuf && (pivot || query.as_percent.unwrap_or(false)) {
+        let option = if pivot { "pivot" } else { "as_percent" };
+        return Err(Error::InvalidRequest(vec![FieldError::new(
+            option.to_string(),
+            "is not available as application/x-protobuf".to_string(),
+        )]));
+    }
+    let stats = match query.stats.as_deref() {
+        None => false,
+        Some("ci") if pivot || query.as_percent.unwrap_or(false) || accept_protobuf => {
+            return Err(Error::InvalidRequest(vec![FieldError::new(
+                "stats".to_string(),
+                "cannot be combined with pivot, as_percent or application/x-protobuf".to_string(),
+            )]))
+        }
+        Some("ci") => true,
+        Some(_) => {
+            return Err(Error::InvalidRequest(vec![FieldError::new(
+                "stats".to_string(),
+                "expected ci".to_string(),
+            )]))
+        }
+    };
+    let from_graph = match query.source.as_deref() {
+        None | Some("table") => false,
+        Some("graph") => true,
+        Some(_) => {
+            return Err(Error::InvalidRequest(vec![FieldError::new(
+                "source".to_string(),
+                "expected table or graph".to_string(),
+            )]))
+        }
+    };
+    let weight_by = match query.weight_by.as_deref() {
+        None => aggregation::WeightBy::default(),
+        Some(name) => name.parse().map_err(|e| {
+            Error::InvalidRequest(vec![FieldError::new("weight_by".to_string(), e)])
+        })?,
+    };
+    // The stats are computed from the stored scores with their own mean.
+    if stats && (from_graph || query.weight_by.is_some()) {
+        return Err(Error::InvalidRequest(vec![FieldError::new(
+            "stats".to_string(),
+            "cannot be combined with source=graph or weight_by".to_string(),
+        )]));
+    }
+    if from_graph && weight_by != aggregation::WeightBy::Datasets {
+        return Err(Error::InvalidRequest(vec![FieldError::new(
+            "weight_by".to_string(),
+            "only datasets is available with source=graph".to_string(),
+        )]));
+    }
+    let mut data = serde_json::from_str::<DatasetsRequest>(from_utf8(&body)?)?;
+    validate_dataset_uris(&data.datasets)?;
+    if let Some(thresholds) = &data.dimension_thresholds {
+        validate_dimension_thresholds(thresholds)?;
+    }
+    if let Some(exclude) = &data.exclude {
+        let exclude: HashSet<&String> = exclude.iter().collect();
+        data.datasets
+            .retain(|dataset_uri| !exclude.contains(dataset_uri));
+    }
+    let dimension_aggregation = match data.aggregation.as_deref() {
+        None => aggregation::Aggregation::default(),
+        Some(name) => name.parse().map_err(|e| {
+            Error::InvalidRequest(vec![FieldError::new("aggregation".to_string(), e)])
+        })?,
+    };
+    if stats && data.aggregation.is_some() {
+        return Err(Error::InvalidRequest(vec![FieldError::new(
+            "stats".to_string(),
+            "cannot be combined with aggregation".to_string(),
+        )]));
+    }
+    if from_graph && dimension_aggregation != aggregation::Aggregation::Mean {
+        return Err(Error::InvalidRequest(vec![FieldError::new(
+            "aggregation".to_string(),
+            "only mean is available with source=graph".to_string(),
+        )]));
+    }
+    if let Some(percentiles) = &data.percentiles {
+        validate_percentiles(percentiles)?;
+        if from_graph {
+            return Err(Error::InvalidRequest(vec![FieldError::new(
+                "percentiles".to_string(),
+                "not available with source=graph".to_string(),
+            )]));
+        }
+        // Only the plain DatasetsScores response carries the percentiles.
+        if stats || pivot || query.as_percent.unwrap_or(false) {
+            return Err(Error::InvalidRequest(vec![FieldError::new(
+                "percentiles".to_string(),
+                "cannot be combined with stats, pivot or as_percent".to_string(),
+            )]));
+        }
+    }
+
+    let dimension_ids = data.dimensions.take().unwrap_or_default();
+    let requested_dimension_ids = dimension_ids.clone();
+    // The pivoted response has no aggregations, and stats computes its own.
+    let with_aggregations = !(stats || pivot);
+    // Only the plain DatasetsScores response carries the timestamps and dimensions.
+    let per_dataset = !(stats || pivot || query.as_percent.unwrap_or(false));
+
+    let result: Result<DatasetsScores, Error> = web::block(move || {
+        // Obtaining a connection from the pool is also a potentially blocking operation.
+        // So, it should be called within the `web::block` closure, as well.
+        let mut conn = pool.get(queries.into_inner())?;
+
+        let mut scores = conn.json_scores(&data.datasets)?;
+        let mut datasets = data.datasets;
+        if let Some(thresholds) = &data.dimension_thresholds {
+            let passing = aggregation::meeting_thresholds(&scores, thresholds);
+            scores.retain(|dataset_uri, _| passing.contains(dataset_uri));
+            datasets.retain(|dataset_uri| passing.contains(dataset_uri));
+        }
+        let aggregations = if !with_aggregations {
+            Vec::new()
+        } else if from_graph {
+            let graphs = conn.turtle_assessments(&datasets)?;
+            let mut aggregations = aggregation::graph_aggregates(&graphs, &scores)?;
+            if !dimension_ids.is_empty() {
+                aggregations.retain(|aggregate| dimension_ids.contains(&aggregate.id));
+            }
+            aggregations
+        } else {
+            conn.dimension_aggregates(&datasets, &dimension_ids, dimension_aggregation, weight_by)?
+        };
+        let (timestamps, dimensions) = if per_dataset {
+            let timestamps = conn.assessment_timestamps(&datasets)?;
+            let dimensions = conn
+                .dataset_dimensions(&datasets)?
+                .into_iter()
+                .map(|(dataset_uri, rows)| {
+                    let rows = rows
+                        .into_iter()
+                        .map(|row| models::DatasetDimension::new(row.id, row.score, row.max_score))
+                        .collect();
+                    (dataset_uri, rows)
+                })
+                .collect();
+            (timestamps, dimensions)
+        } else {
+            Default::default()
+        };
+        let percentiles = match &data.percentiles {
+            Some(percentiles) => Some(conn.dimension_percentiles(&datasets, percentiles)?),
+            None => None,
+        };
+        Ok(models::DatasetsScores {
+            scores,
+            aggregations,
+            timestamps,
+            dimensions,
+            percentiles,
+        })
+    })
+    .await
+    .map_err(Error::BlockingError)?;
+
+    let mut scores = result?;
+    scores
+        .scores
+        .values_mut()
+        .for_each(|score| aggregation::fill_ratios(score, *OVERALL_STRATEGY));
+    if accept_protobuf {
+        let mut response = HttpResponse::Ok();
+        response.content_type("application/x-protobuf");
+        return Ok(signed(
+            response,
+            proto::DatasetsScores::from(scores).encode_to_vec(),
+        ));
+    }
+    let body = if stats {
+        serde_json::to_string(&models::DatasetsStatsScores {
+            aggregations: aggregation::dimension_stats(&scores.scores, &requested_dimension_ids),
+            scores: scores.scores,
+        })?
+    } else if pivot {
+        serde_json::to_string(&models::DimensionsScores {
+            dimensions: aggregation::pivot_by_dimension(&scores.scores, &requested_dimension_ids),
+        })?
+    } else if query.as_percent.unwrap_or(false) {
+        serde_json::to_string(&models::DatasetsPercentScores {
+            aggregations: scores
+                .aggregations
+                .iter()
+                .map(|aggregate| models::DimensionPercentAggregate {
+                    id: aggregate.id.clone(),
+                    score_percent: aggregation::score_percent(
+                        aggregate.score,
+                        aggregate.max_score,
+                        *PERCENT_DECIMALS,
+                    ),
+                })
+                .collect(),
+            scores: scores.scores,
+        })?
+    } else {
+        serde_json::to_string(&scores)?
+    };
+
+    let mut response = HttpResponse::Ok();
+    response.content_type(mime::APPLICATION_JSON);
+    Ok(signed(response, body.into_bytes()))
+}
+
+#[post("/api/scores/percentile-rank")]
+async fn percentile_rank(
+    request: HttpRequest,
+    pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
+    body: web::Bytes,
+) -> Result<impl Responder, Error> {
+    validate_read_access(request)?;
+    let data = serde_json::from_str::<models::PercentileRankRequest>(from_utf8(&body)?)?;
+    let mut errors = Vec::new();
+    if let Err(e) = data.dataset_uri.parse::<Uri>() {
+        errors.push(FieldError::new("dataset_uri".to_string(), e.to_string()));
+    }
+    for (i, uri) in data.peer_uris.iter().enumerate() {
+        if let Err(e) = uri.parse::<Uri>() {
+            errors.push(FieldError::new(format!("peer_uris[{i}]"), e.to_string()));
+        }
+    }
+    if data.dimension.is_empty() {
+        errors.push(FieldError::new(
+            "dimension".to_string(),
+            "dimension id is required".to_string(),
+        ));
+    }
+    if !errors.is_empty() {
+        return Err(Error::InvalidRequest(errors));
+    }
+
+    let result = web::block(move || {
+        // Obtaining a connection from the pool is also a potentially blocking operation.
+        // So, it should be called within the `web::block` closure, as well.
+        let mut conn = pool.get(queries.into_inner())?;
+        let mut uris = data.peer_uris.clone();
+        uris.push(data.dataset_uri.clone());
+        let dataset_scores = conn.json_scores(&uris)?;
+        Ok::<_, DatabaseError>(aggregation::percentile_rank(
+            &dataset_scores,
+            &data.dataset_uri,
+            &data.peer_uris,
+            &data.dimension,
+        ))
+    })
+    .await
+    .map_err(Error::BlockingError)?;
+
+    match result {
+        Ok(Some(rank)) => Ok(HttpResponse::Ok()
+            .content_type(mime::APPLICATION_JSON)
+            .message_body(serde_json::to_string(&rank)?)),
+        Ok(None) => Err(Error::InvalidRequest(vec![FieldError::new(
+            "dataset_uri".to_string(),
+            "dataset is not scored in the dimension".to_string(),
+        )])),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Dimension scores of two datasets side by side, with the difference b minus a
+/// where both score a dimension.
+#[post("/api/scores/compare")]
+async fn compare_datasets(
+    request: HttpRequest,
+    pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
+    body: web::Bytes,
+) -> Result<impl Responder, Error> {
+    validate_read_access(request)?;
+    let data = serde_json::from_str::<models::DatasetComparisonRequest>(from_utf8(&body)?)?;
+    let errors: Vec<FieldError> = [("a", &data.a), ("b", &data.b)]
+        .into_iter()
+        .filter_map(|(field, uri)| {
+            uri.parse::<Uri>()
+                .err()
+                .map(|e| FieldError::new(field.to_string(), e.to_string()))
+        })
+        .collect();
+    if !errors.is_empty() {
+        return Err(Error::InvalidRequest(errors));
+    }
+
+    let uris = vec![data.a.clone(), data.b.clone()];
+    let mut dataset_scores = web::block(move || {
+        // Obtaining a connection from the pool is also a potentially blocking operation.
+        // So, it should be called within the `web::block` closure, as well.
+        let mut conn = pool.get(queries.into_inner())?;
+        conn.json_scores(&uris)
+    })
+    .await
+    .map_err(Error::BlockingError)??;
+
+    let mut score = |uri: &String| {
+        dataset_scores
+            .remove(uri)
+            .ok_or_else(|| Error::DatasetNotScored(uri.clone()))
+    };
+    let a = score(&data.a)?;
+    // The same uri twice compares a dataset with itself.
+    let b = if data.b == data.a {
+        a.clone()
+    } else {
+        score(&data.b)?
+    };
+
+    let mut dimensions: BTreeMap<&str, models::DatasetDimensionComparison> = BTreeMap::new();
+    for dimension in &a.dataset.dimensions {
+        let comparison = dimensions
+            .entry(&dimension.id)
+            .or_insert_with(|| models::DatasetDimensionComparison::new(dimension.id.clone()));
+        comparison.a_score = Some(dimension.score);
+        comparison.a_max_score = Some(dimension.max_score);
+    }
+    for dimension in &b.dataset.dimensions {
+        let comparison = dimensions
+            .entry(&dimension.id)
+            .or_insert_with(|| models::DatasetDimensionComparison::new(dimension.id.clone()));
+        comparison.b_score = Some(dimension.score);
+        comparison.b_max_score = Some(dimension.max_score);
+        comparison.delta = comparison
+            .a_score
+            .map(|a| dimension.score.saturating_sub(a));
+    }
+
+    let comparison = models::DatasetComparison::new(
+        data.a.clone(),
+        data.b.clone(),
+        dimensions.into_values().collect(),
+    );
+    Ok(HttpResponse::Ok()
+        .content_type(mime::APPLICATION_JSON)
+        .message_body(serde_json::to_string(&comparison)?))
+}
+
+#[derive(Deserialize)]
+struct StreamAggregationsQuery {
+    batch_size: Option<NonZeroUsize>,
+}
+
+/// Dimension aggregates of the given datasets as Server-Sent Events: a
+/// `progress` event after every batch of `batch_size` datasets (by default
+/// `AGGREGATION_BATCH_SIZE`), then an `aggregates` event with the aggregations
+/// `POST /api/scores` returns with the default mean aggregation.
+#[post("/api/aggregations/stream")]
+async fn stream_aggregations(
+    request: HttpRequest,
+    query: web::Query<StreamAggregationsQuery>,
+    pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
+    body: web::Bytes,
+) -> Result<impl Responder, Error> {
+    validate_read_access(request)?;
+    let data = serde_json::from_str::<DatasetsRequest>(from_utf8(&body)?)?;
+    validate_dataset_uris(&data.datasets)?;
+    // A dataset listed twice would otherwise be counted in two batches.
+    let mut seen = HashSet::new();
+    let mut datasets = data.datasets;
+    datasets.retain(|dataset_uri| seen.insert(dataset_uri.clone()));
+
+    let mut conn = web::block(move || pool.get(queries.into_inner()))
+        .await
+        .map_err(Error::BlockingError)??;
+
+    let batch_size = query.batch_size.unwrap_or(*AGGREGATION_BATCH_SIZE).get();
+    let (events, stream) = sse::channel();
+    actix_web::rt::task::spawn_blocking(move || {
+        let total = datasets.len() as i64;
+        let mut processed = 0;
+        let mut sums = aggregation::AggregateSums::default();
+        for batch in datasets.chunks(batch_size) {
+            match conn.dimension_sums(batch) {
+                Ok(batch_sums) => sums.add(batch_sums),
+                Err(e) => {
+                    events.send(
+                        "error",
+                        &models::Error {
+                            error: Some(e.to_string()),
+                            ..Default::default()
+                        },
+                    );
+                    return;
+                }
+            }
+            processed += batch.len() as i64;
+            if !events.send(
+                "progress",
+                &models::AggregationProgress::new(processed, total),
+            ) {
+                return;
+            }
+        }
+        events.send("aggregates", &sums.aggregates());
+    });
+
+    Ok(HttpResponse::Ok()
+        .content_type(mime::TEXT_EVENT_STREAM)
+        .insert_header(header::CacheControl(vec![header::CacheDirective::NoCache]))
+        // Compress would hold events back until enough of them fill a block.
+        .insert_header(header::ContentEncoding::Identity)
+        .message_body(stream))
+}
+
+/// Scores and graphs of the given datasets as a tar archive with a
+/// `<uri-hash>.json` and `<uri-hash>.ttl` entry per dataset, streamed while
+/// the rows are read.
+#[post("/api/assessments/full-export")]
+async fn full_export(
+    request: HttpRequest,
+    pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
+    body: web::Bytes,
+) -> Result<impl Responder, Error> {
+    validate_api_key(request)?;
+    let data = serde_json::from_str::<DatasetsRequest>(from_utf8(&body)?)?;
+    validate_dataset_uris(&data.datasets)?;
+
+    let mut conn = web::block(move || pool.get(queries.into_inner()))
+        .await
+        .map_err(Error::BlockingError)??;
+
+    let (writer, archive) = archive::channel();
+    actix_web::rt::task::spawn_blocking(move || {
+        let result = conn.for_each_export(&data.datasets, |dataset_uri, mut score, turtle| {
+            aggregation::fill_ratios(&mut score, *OVERALL_STRATEGY);
+            let json = serde_json::to_vec(&score)?;
+            let stem = archive::uri_hash(&dataset_uri);
+            Ok(writer.append(&format!("{stem}.json"), &json)
+                && writer.append(&format!("{stem}.ttl"), turtle.as_bytes()))
+        });
+        writer.finish(result);
+    });
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/x-tar")
+        .message_body(archive))
+}
+
+/// Dimension scores of every stored dataset as CSV: a row per dataset and a
+/// column per dimension id in the store, read in a first pass, with the cells
+/// of dimensions a dataset lacks left empty. Streamed while the rows are read.
+#[get("/api/scores/stream.csv")]
+async fn stream_scores_csv(
+    request: HttpRequest,
+    pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
+) -> Result<impl Responder, Error> {
+    validate_api_key(request)?;
+
+    let mut conn = web::block(move || pool.get(queries.into_inner()))
+        .await
+        .map_err(Error::BlockingError)??;
+
+    let (writer, document) = csv::channel();
+    actix_web::rt::task::spawn_blocking(move || {
+        let result = conn.dimension_ids().and_then(|dimension_ids| {
+            let mut header = vec!["dataset_uri"];
+            header.extend(dimension_ids.iter().map(String::as_str));
+            if !writer.row(&header) {
+                return Ok(());
+            }
+            let columns: HashMap<&str, usize> = dimension_ids
+                .iter()
+                .enumerate()
+                .map(|(i, id)| (id.as_str(), i + 1))
+                .collect();
+            conn.for_each_dataset_scores(|dataset_uri, dimension_scores| {
+                let mut row = vec![String::new(); header.len()];
+                row[0] = dataset_uri;
+                for (id, score) in dimension_scores {
+                    // Dimensions stored after the first pass have no column.
+                    if let Some(&column) = columns.get(id.as_str()) {
+                        row[column] = score.to_string();
+                    }
+                }
+                writer.row(&row)
+            })
+        });
+        writer.finish(result);
+    });
+
+    Ok(HttpResponse::Ok()
+        .content_type(mime::TEXT_CSV_UTF_8)
+        .message_body(document))
+}
+
+/// Mean dimension scores of the datasets in a `DatasetsRequest` as CSV, a row
+/// per dimension. The request is filtered as for `/api/scores`, but always
+/// aggregated by mean.
+#[post("/api/scores/export.csv")]
+async fn export_scores_csv(
+    request: HttpRequest,
+    pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
+    body: web::Bytes,
+) -> Result<impl Responder, Error> {
+    validate_read_access(request)?;
+    let mut data = serde_json::from_str::<DatasetsRequest>(from_utf8(&body)?)?;
+    validate_dataset_uris(&data.datasets)?;
+    if let Some(thresholds) = &data.dimension_thresholds {
+        validate_dimension_thresholds(thresholds)?;
+    }
+    if let Some(exclude) = &data.exclude {
+        let exclude: HashSet<&String> = exclude.iter().collect();
+        data.datasets
+            .retain(|dataset_uri| !exclude.contains(dataset_uri));
+    }
+
+    let aggregates = web::block(move || {
+        // Obtaining a connection from the pool is also a potentially blocking operation.
+        // So, it should be called within the `web::block` closure, as well.
+        let mut conn = pool.get(queries.into_inner())?;
+
+        let mut datasets = data.datasets;
+        if let Some(thresholds) = &data.dimension_thresholds {
+            let dataset_scores = conn.json_scores(&datasets)?;
+            let passing = aggregation::meeting_thresholds(&dataset_scores, thresholds);
+            datasets.retain(|dataset_uri| passing.contains(dataset_uri));
+        }
+        conn.dimension_aggregates(
+            &datasets,
+            &data.dimensions.unwrap_or_default(),
+            aggregation::Aggregation::Mean,
+            aggregation::WeightBy::Datasets,
+        )
+    })
+    .await
+    .map_err(Error::BlockingError)??;
+
+    let mut document = csv::record(&["dimension_id", "avg_score", "avg_max_score", "ratio"]);
+    for aggregate in aggregates {
+        document.push_str(&csv::record(&[
+            aggregate.id,
+            aggregate.score.to_string(),
+            aggregate.max_score.to_string(),
+            aggregate.ratio.unwrap_or_default().to_string(),
+        ]));
+    }
+
+    Ok(HttpResponse::Ok()
+        .content_type(mime::TEXT_CSV_UTF_8)
+        .body(document))
+}
+
+/// Buckets of `POST /api/scores/histogram` when the request does not say.
+const DEFAULT_HISTOGRAM_BUCKETS: i32 = 4;
+
+/// Number of datasets in a `DatasetsRequest` per bucket of overall ratio,
+/// filtered as for `/api/scores`.
+#[post("/api/scores/histogram")]
+async fn score_histogram(
+    request: HttpRequest,
+    pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
+    body: web::Bytes,
+) -> Result<impl Responder, Error> {
+    validate_read_access(request)?;
+    let mut data = serde_json::from_str::<DatasetsRequest>(from_utf8(&body)?)?;
+    validate_dataset_uris(&data.datasets)?;
+    if let Some(thresholds) = &data.dimension_thresholds {
+        validate_dimension_thresholds(thresholds)?;
+    }
+    let buckets = data.buckets.unwrap_or(DEFAULT_HISTOGRAM_BUCKETS);
+    if !(1..=100).contains(&buckets) {
+        return Err(Error::InvalidRequest(vec![FieldError::new(
+            "buckets".to_string(),
+            format!("expected between 1 and 100, got {buckets}"),
+        )]));
+    }
+    if let Some(exclude) = &data.exclude {
+        let exclude: HashSet<&String> = exclude.iter().collect();
+        data.datasets
+            .retain(|dataset_uri| !exclude.contains(dataset_uri));
+    }
+
+    let mut dataset_scores = web::block(move || {
+        // Obtaining a connection from the pool is also a potentially blocking operation.
+        // So, it should be called within the `web::block` closure, as well.
+        let mut conn = pool.get(queries.into_inner())?;
+        conn.json_scores(&data.datasets)
+    })
+    .await
+    .map_err(Error::BlockingError)??;
+
+    if let Some(thresholds) = &data.dimension_thresholds {
+        let passing = aggregation::meeting_thresholds(&dataset_scores, thresholds);
+        dataset_scores.retain(|dataset_uri, _| passing.contains(dataset_uri));
+    }
+    Ok(HttpResponse::Ok()
+        .content_type(mime::APPLICATION_JSON)
+        .message_body(serde_json::to_string(&aggregation::ratio_histogram(
+            &dataset_scores,
+            buckets as usize,
+            *OVERALL_STRATEGY,
+        ))?))
+}
+
+#[route("/api/assessments", method = "POST", method = "HEAD")]
+async fn assessments(
+    request: HttpRequest,
+    accept: web::Header<header::Accept>,
+    pool: web::Data<PgPool>,
+    queries: web::ReqData<QueryCount>,
+    body: web::Bytes,
+) -> Result<impl Responder, Error> {
+    validate_read_access(request.clone())?;
+    // HEAD requests rarely carry a body; without one no datasets are asked for.
+    let data = if body.is_empty() {
+        DatasetsRequest::default()
+    } else {
+        serde_json::from_str::<DatasetsRequest>(from_utf8(&body)?)?
+    };
+    validate_dataset_uris(&data.datasets)?;
+    let media_type = negotiate(
+        &accept,
+        &[TEXT_TURTLE, APPLICATION_TRIG, APPLICATION_LD_JSON],
+    )?;
+
+    let result: Result<(String, Vec<String>), Error> = web::block(move || {
+        // Obtaining a connection from the pool is also a potentially blocking operation.
+        // So, it should be called within the `web::block` closure, as well.
+        let mut conn = pool.get(queries.into_inner())?;
+
+        match media_type {
+            APPLICATION_TRIG => graph::trig_dataset(&conn.turtle_assessments(&data.datasets)?),
+            APPLICATION_LD_JSON => graph::merged_jsonld(&conn.jsonld_assessments(&data.datasets)?),
+            _ => graph::merged_turtle(&conn.turtle_assessments(&data.datasets)?),
+        }
+    })
+    .await
+    .map_err(Error::BlockingError)?;
+
+    match result {
+        Ok((graph, skipped)) => {
+            let mut response = HttpResponse::Ok();
+            response.content_type(media_type);
+            if !skipped.is_empty() {
+                response.insert_header((SKIPPED_DATASETS_HEADER, skipped.join(",")));
+            }
+            if request.method() == Method::HEAD {
+                return Ok(response.message_body(String::new()));
+            }
+            Ok(response.message_body(graph))
+        }
+        Err(e) => Err(e),
+    }
+}
+
+fn cors() -> Cors {
+    let origins: Vec<String> = ALLOWED_ORIGINS
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .collect();
+
+    Cors::default()
+        .allowed_origin_fn(move |origin, _req_head| {
+            let origin_str = origin.to_str().unwrap_or_default();
+
+            for allowed in &origins {
+                if allowed == origin_str {
+                    return true;
+                }
+
+                if allowed.starts_with("*.")
+                    && origin_str.ends_with(allowed.trim_start_matches('*'))
+                {
+                    return true;
+                }
+
+                if allowed.ends_with(":*") && origin_str.starts_with(allowed.trim_end_matches(":*"))
+                {
+                    return true;
+                }
+            }
+
+            false
+        })
+        .allowed_methods(["GET", "POST", "DELETE"])
+        .allow_any_header()
+        // Paging headers, read by browser clients of the listings, and the
+        // entity tag of assessment graphs for conditional requests.
+        .expose_headers([
+            header::LINK.as_str(),
+            TOTAL_COUNT_HEADER,
+            header::ETAG.as_str(),
+        ])
+        .max_age(3600)
+}
+
+/// Records the response and the queries issued for it in `RequestMetrics`,
+/// when registered, labelled with the route pattern the request matched.
+async fn record_metrics(
+    request: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, actix_web::Error> {
+    let started = Instant::now();
+    let response = next.call(request).await?.map_into_boxed_body();
+
+    let request = response.request();
+    if let Some(request_metrics) = request.app_data::<web::Data<RequestMetrics>>() {
+        let queries = request
+            .extensions()
+            .get::<QueryCount>()
+            .map(QueryCount::durations)
+            .unwrap_or_default();
+        request_metrics.record(
+            request.method().as_str(),
+            request.match_pattern().as_deref().unwrap_or("unmatched"),
+            response.status().as_u16(),
+            started.elapsed(),
+            &queries,
+        );
+    }
+    Ok(response)
+}
+
+/// Echoes the id of the request on the response, and records the response in
+/// `RecentErrors` when it is a server error.
+async fn record_errors(
+    request: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, actix_web::Error> {
+    let request_id = request
+        .extensions()
+        .get::<RequestId>()
+        .map_or_else(|| Uuid::new_v4().to_string(), |id| id.0.clone());
+    let recent_errors = request.app_data::<web::Data<RecentErrors>>().cloned();
+    let method = request.method().to_string();
+    let path = request.path().to_string();
+
+    let mut response = next.call(request).await?.map_into_boxed_body();
+    if let (Ok(name), Ok(value)) = (
+        header::HeaderName::try_from(REQUEST_ID_HEADER),
+        header::HeaderValue::from_str(&request_id),
+    ) {
+        response.headers_mut().insert(name, value);
+    }
+    let status = response.status();
+    if let (true, Some(recent_errors)) = (status.is_server_error(), recent_errors) {
+        recent_errors.push(models::RecentError {
+            timestamp: Utc::now().to_rfc3339(),
+            request_id,
+            method,
+            path,
+            status: status.as_u16() as i32,
+            error: response
+                .response()
+                .error()
+                .map_or_else(|| status.to_string(), |e| e.to_string()),
+        });
+    }
+    Ok(response)
+}
+
+/// Id of a request, stored in its extensions by `trace_requests`.
+#[derive(Clone)]
+struct RequestId(String);
+
+/// Runs the request in a span carrying its id, taken from the client or
+/// generated, so every log line of the request can be correlated across
+/// services. The number of queries issued is added to the span as
+/// `db_query_count` once the request completes. The request headers are
+/// recorded with the values in `REDACT_HEADERS` masked.
+async fn trace_requests(
+    request: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, actix_web::Error> {
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|id| id.to_str().ok())
+        .map_or_else(|| Uuid::new_v4().to_string(), str::to_string);
+    request
+        .extensions_mut()
+        .insert(RequestId(request_id.clone()));
+    let queries = QueryCount::default();
+    request.extensions_mut().insert(queries.clone());
+
+    let span = tracing::info_span!(
+        "request",
+        request_id = request_id.as_str(),
+        method = request.method().as_str(),
+        path = request.path(),
+        headers = REDACT_HEADERS.format(request.headers()).as_str(),
+        db_query_count = tracing::field::Empty,
+    );
+    let response = next.call(request).instrument(span.clone()).await;
+
+    span.record("db_query_count", queries.get());
+    span.in_scope(|| tracing::debug!("request completed"));
+
+    Ok(response?.map_into_boxed_body())
+}
+
+/// Renders error responses again in the language preferred by `Accept-Language`.
+async fn localize_errors(
+    request: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, actix_web::Error> {
+    let language = request
+        .get_header::<header::AcceptLanguage>()
+        .map_or(Language::English, |accept| Language::negotiate(&accept));
+    let response = next.call(request).await?;
+    if language == Language::English {
+        return Ok(response.map_into_boxed_body());
+    }
+
+    let localized = response
+        .response()
+        .error()
+        .and_then(|e| e.as_error::<Error>())
+        .map(|e| e.localized_response(language));
+    Ok(match localized {
+        Some(localized) => response.into_response(localized),
+        None => response.map_into_boxed_body(),
+    })
+}
+
+/// State shared by the workers, handed to `app` once per worker. The defaults
+/// are fresh, empty instances, as the tests use them.
+#[derive(Clone, Default)]
+struct AppState {
+    write_freeze: WriteFreeze,
+    gauges: DimensionGauges,
+    recent_errors: RecentErrors,
+    read_access: ReadAccess,
+    /// Request metrics are only recorded when set.
+    request_metrics: Option<RequestMetrics>,
+}
+
+fn app(
+    state: AppState,
+) -> App<
+    impl ServiceFactory<
+        ServiceRequest,
+        Response = ServiceResponse<impl MessageBody>,
+        Error = actix_web::Error,
+        Config = (),
+        InitError = (),
+    >,
+> {
+    let pool = PgPool::new().unwrap();
+
+    let openapi = serde_yaml::from_str::<OpenApi>(include_str!("../openapi.yaml")).unwrap();
+    let yaml = OpenApiYaml(serde_yaml::to_string(&openapi).unwrap());
+
+    let app = App::new()
+        .wrap(from_fn(localize_errors))
+        .wrap(from_fn(record_errors))
+        .wrap(from_fn(trace_requests))
+        .wrap(from_fn(record_metrics))
+        .wrap(cors())
+        .wrap(Compress::default())
+        .wrap(NormalizePath::trim())
+        .app_data(web::PayloadConfig::default().limit(8_388_608))
+        .app_data(web::QueryConfig::default().error_handler(|e, _| invalid_part("query", e)))
+        .app_data(web::PathConfig::default().error_handler(|e, _| invalid_part("path", e)))
+        .app_data(web::JsonConfig::default().error_handler(|e, _| invalid_part("body", e)))
+        .app_data(web::Data::new(pool.clone()))
+        .app_data(web::Data::new(state.write_freeze))
+        .app_data(web::Data::new(state.gauges))
+        .app_data(web::Data::new(state.recent_errors))
+        .app_data(web::Data::new(state.read_access))
+        .app_data(web::Data::new(yaml))
+        .service(ping)
+        .service(ready)
+        .service(replication_health)
+        .service(list_assessments)
+        .service(changed_assessments)
+        .service(full_export)
+        .service(stream_scores_csv)
+        .service(export_scores_csv)
+        .service(score_histogram)
+        .service(assessment_graph)
+        .service(assessment_graph_version)
+        .service(dimension_trend)
+        .service(assessment_distributions)
+        .service(assessment_score)
+        .service(assessment_uri)
+        .service(annotations)
+        .service(set_annotation)
+        .service(delete_annotation)
+        .service(bulk_update_assessments)
+        .service(update_assessment)
+        .service(sparql_update)
+        .service(assessments)
+        .service(scores)
+        .service(percentile_rank)
+        .service(compare_datasets)
+        .service(stream_aggregations)
+        .service(dimensions_overview)
+        .service(catalog_counts)
+        .service(compare_catalogs)
+        .service(vs_average)
+        .service(publisher_aggregate)
+        .service(score_schema)
+        .service(warm_aggregates)
+        .service(set_write_freeze)
+        .service(validate_graphs)
+        .service(prune_orphans)
+        .service(list_recent_errors)
+        .service(pool_state)
+        .service(reset)
+        .service(metrics)
+        .service(openapi_yaml)
+        // Trailing slashes are trimmed, so `/swagger-ui/` arrives as `/swagger-ui` and
+        // would miss the wildcard route. Redirecting to the index keeps relative asset
+        // urls resolving below `/swagger-ui/`.
+        .service(web::redirect("/swagger-ui", "/swagger-ui/index.html"))
+        .service(SwaggerUi::new("/swagger-ui/{_:.*}").url("/openapi.json", openapi.clone()));
+    match state.request_metrics {
+        Some(request_metrics) => app.app_data(web::Data::new(request_metrics)),
+        None => app,
+    }
+}
+
+#[actix_web::main]
+async fn main() -> std::io::Result<()> {
+    tracing_subscriber::fmt()
+        .json()
+        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+        .with_target(false)
+        .with_current_span(false)
+        .init();
+
+    tracing::debug!("Tracing initialized");
+
+    if let Err(e) = migrate_database() {
+        tracing::error!(error = e.to_string().as_str(), "database migration failed");
+        std::process::exit(1)
+    }
+
+    // Fail if API_KEY missing
+    let _ = API_KEY.clone();
+    // Fail if the page sizes are invalid
+    let _ = *PAGE_SIZES;
+
+    let write_freeze = WriteFreeze::new(env::var("WRITE_FREEZE_UNTIL").ok().map(|until| {
+        parse_timestamp("WRITE_FREEZE_UNTIL", &until).unwrap_or_else(|e| {
+            tracing::error!(error = e.message.as_str(), "WRITE_FREEZE_UNTIL is invalid");
+            std::process::exit(1)
+        })
+    }));
+
+    let gauges = DimensionGauges::default();
+    if *METRICS_INTERVAL_SECS > 0 {
+        let pool = PgPool::new().unwrap();
+        let gauges = gauges.clone();
+        actix_web::rt::spawn(async move {
+            let mut interval =
+                actix_web::rt::time::interval(Duration::from_secs(*METRICS_INTERVAL_SECS));
+            loop {
+                interval.tick().await;
+                if let Err(e) = refresh_gauges(pool.clone(), &gauges).await {
+                    tracing::error!(error = e.to_string().as_str(), "refreshing gauges failed");
+                }
+            }
+        });
+    }
+
+    let state = AppState {
+        write_freeze,
+        gauges,
+        recent_errors: RecentErrors::new(*RECENT_ERRORS_CAPACITY),
+        read_access: ReadAccess {
+            require_api_key: *REQUIRE_API_KEY_FOR_READS,
+        },
+        request_metrics: Some(RequestMetrics::default()),
+    };
+    HttpServer::new(move || app(state.clone()).wrap(Logger::default()))
+        .bind(("0.0.0.0", 8082))?
+        .run()
+        .await
+}
+
+/// Media type of a response: the one of `offered` the client gives the highest
+/// quality in its `Accept` header, the earliest offered on ties. A type gets the
+/// quality of the most specific range matching it, so wildcards count too, and
+/// without an `Accept` header the first offered is used. Answers 406 when the
+/// client accepts none of them.
+fn negotiate(accept: &header::Accept, offered: &[&'static str]) -> Result<&'static str, Error> {
+    if accept.0.is_empty() {
+        return Ok(offered[0]);
+    }
+    let quality = |media_type: &str| {
+        let type_ = media_type.split('/').next().unwrap_or_default();
+        accept
+            .0
+            .iter()
+            .filter_map(|qi| {
+                let range = &qi.item;
+                let specificity = if range.essence_str() == media_type {
+                    2
+                } else if range.type_() == type_ && range.subtype() == mime::STAR {
+                    1
+                } else if range.type_() == mime::STAR && range.subtype() == mime::STAR {
+                    0
+                } else {
+                    return None;
+                };
+                Some((specificity, qi.quality))
+            })
+            .max_by_key(|(specificity, _)| *specificity)
+            .map_or(header::Quality::ZERO, |(_, quality)| quality)
+    };
+    let mut chosen = None;
+    let mut best = header::Quality::ZERO;
+    for &media_type in offered {
+        let q = quality(media_type);
+        if q > best {
+            chosen = Some(media_type);
+            best = q;
+        }
+    }
+    chosen.ok_or_else(|| Error::NotAcceptable(offered.to_vec()))
+}
+
+/// Extractor failures answered with the validation envelope, reported against
+/// the part of the request that did not parse.
+fn invalid_part(part: &str, error: impl Display) -> actix_web::Error {
+    Error::InvalidRequest(vec![FieldError::new(part.to_string(), error.to_string())]).into()
+}
+
+fn parse_uuid(uuid: String) -> Result<Uuid, Error> {
+    Uuid::parse_str(uuid.as_ref()).map_err(|_| Error::InvalidID(uuid))
+}
+
+fn parse_timestamp(field: &str, timestamp: &str) -> Result<DateTime<Utc>, FieldError> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+        .map_err(|e| {
+            FieldError::new(
+                field.to_string(),
+                format!("invalid RFC 3339 timestamp: {e}"),
+            )
+        })
+}
+
+/// Check that every minimum ratio in the thresholds is between 0 and 1.
+/// Every ratio outside that range is reported as a separate field error.
+fn validate_dimension_thresholds(thresholds: &HashMap<String, f64>) -> Result<(), Error> {
+    let mut errors: Vec<FieldError> = thresholds
+        .iter()
+        .filter(|(_, min_ratio)| !(0.0..=1.0).contains(*min_ratio))
+        .map(|(id, min_ratio)| {
+            FieldError::new(
+                format!("dimension_thresholds.{id}"),
+                format!("expected a ratio between 0 and 1, got {min_ratio}"),
+            )
+        })
+        .collect();
+    errors.sort_by(|a, b| a.field.cmp(&b.field));
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::InvalidRequest(errors))
+    }
+}
+
+/// Check that uris are valid, but disregard parsed value.
+/// Every invalid uri is reported as a separate field error.
+fn validate_dataset_uris(dataset_uris: &[String]) -> Result<(), Error> {
+    let errors: Vec<FieldError> = dataset_uris
+        .iter()
+        .enumerate()
+        .filter_map(|(i, uri)| {
+            uri.parse::<Uri>()
+                .err()
+                .map(|e| FieldError::new(format!("datasets[{i}]"), e.to_string()))
+        })
+        .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::InvalidRequest(errors))
+    }
+}
+
+fn validate_percentiles(percentiles: &[f64]) -> Result<(), Error> {
+    let errors: Vec<FieldError> = percentiles
+        .iter()
+        .enumerate()
+        .filter(|(_, percentile)| !(0.0..=100.0).contains(*percentile))
+        .map(|(i, percentile)| {
+            FieldError::new(
+                format!("percentiles[{i}]"),
+                format!("expected a percentile between 0 and 100, got {percentile}"),
+            )
+        })
+        .collect();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::PercentileOutOfRange(errors))
+    }
+}
+
+/// Production deployments set ENVIRONMENT to their namespace, `prod`.
+fn ensure_not_production(environment: &str) -> Result<(), Error> {
+    if environment == "prod" || environment == "production" {
+        Err(Error::Forbidden("not allowed in production".to_string()))
+    } else {
+        Ok(())
+    }
+}
+
+/// Scores are stored as 32-bit integers, so larger values are rejected up front
+/// instead of failing deserialization.
+fn validate_score_ranges(body: &serde_json::Value) -> Result<(), Error> {
+    fn walk(value: &serde_json::Value, path: String, errors: &mut Vec<FieldError>) {
+        match value {
+            serde_json::Value::Object(fields) => {
+                for (key, value) in fields {
+                    let path = format!("{path}.{key}");
+                    let is_score = key == "score" || key == "max_score";
+                    if is_score
+                        && value.is_number()
+                        && value.as_i64().and_then(|n| i32::try_from(n).ok()).is_none()
+                    {
+                        errors.push(FieldError::new(
+                            path,
+                            format!("must be an integer between {} and {}", i32::MIN, i32::MAX),
+                        ));
+                    } else {
+                        walk(value, path, errors);
+                    }
+                }
+            }
+            serde_json::Value::Array(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    walk(item, format!("{path}[{i}]"), errors);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut errors = Vec::new();
+    walk(&body["scores"], "scores".to_string(), &mut errors);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::ScoreOutOfRange(errors))
+    }
+}
+
+/// Every dimension of the dataset and its distributions has to have a positive
+/// max score that its score does not exceed, or its ratio would leave 0..=1.
+fn validate_dimension_scores(dataset_score: &models::DatasetScore) -> Result<(), Error> {
+    let all_scores =
+        std::iter::once(("scores.dataset".to_string(), dataset_score.dataset.as_ref())).chain(
+            dataset_score
+                .distributions
+                .iter()
+                .enumerate()
+                .map(|(i, distribution)| (format!("scores.distributions[{i}]"), distribution)),
+        );
+    let mut errors = Vec::new();
+    for (path, score) in all_scores {
+        for (i, dimension) in score.dimensions.iter().enumerate() {
+            if dimension.max_score <= 0 {
+                errors.push(FieldError::new(
+                    format!("{path}.dimensions[{i}].max_score"),
+                    format!("must be greater than 0, got {}", dimension.max_score),
+                ));
+            } else if dimension.score > dimension.max_score {
+                errors.push(FieldError::new(
+                    format!("{path}.dimensions[{i}].score"),
+                    format!(
+                        "{} exceeds the max score of {}",
+                        dimension.score, dimension.max_score
+                    ),
+                ));
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::InvalidScore(errors))
+    }
+}
+
+/// Dimensions outside the MQA vocabulary, reported as warnings rather than
+/// refused, since producers may score dimensions this service does not know yet.
+fn unknown_dimension_warnings(dataset_score: &models::DatasetScore) -> Vec<FieldError> {
+    let dataset = dataset_score
+        .dataset
+        .dimensions
+        .iter()
+        .enumerate()
+        .map(|(i, dimension)| (format!("scores.dataset.dimensions[{i}].id"), &dimension.id));
+    let distributions =
+        dataset_score
+            .distributions
+            .iter()
+            .enumerate()
+            .flat_map(|(i, distribution)| {
+                distribution
+                    .dimensions
+                    .iter()
+                    .enumerate()
+                    .map(move |(j, dimension)| {
+                        (
+                            format!("scores.distributions[{i}].dimensions[{j}].id"),
+                            &dimension.id,
+                        )
+                    })
+            });
+    dataset
+        .chain(distributions)
+        .filter(|(_, id)| !vocab::mqa::DIMENSIONS.contains(&id.as_str()))
+        .map(|(field, id)| {
+            FieldError::new(
+                field,
+                format!("dimension '{id}' is not in the MQA vocabulary"),
+            )
+        })
+        .collect()
+}
+
+/// Resolves dimension ids occurring more than once according to `policy`.
+fn dedup_dimensions(
+    dimensions: &mut Vec<models::Dimension>,
+    policy: DuplicatePolicy,
+) -> Result<(), Error> {
+    let mut first: HashMap<String, usize> = HashMap::new();
+    let mut errors = Vec::new();
+    let mut deduped: Vec<models::Dimension> = Vec::with_capacity(dimensions.len());
+
+    for (i, dimension) in dimensions.drain(..).enumerate() {
+        match first.get(&dimension.id) {
+            None => {
+                first.insert(dimension.id.clone(), deduped.len());
+                deduped.push(dimension);
+            }
+            Some(&position) => match policy {
+                DuplicatePolicy::Reject => errors.push(FieldError::new(
+                    format!("scores.dataset.dimensions[{i}].id"),
+                    format!("duplicate dimension '{}'", dimension.id),
+                )),
+                DuplicatePolicy::First => {}
+                DuplicatePolicy::Last => deduped[position] = dimension,
+            },
+        }
+    }
+
+    *dimensions = deduped;
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::DuplicateDimensions(errors))
+    }
+}
+
+/// Graphs are rejected before any further processing when larger than
+/// `MAX_GRAPH_BYTES`, independent of the generic payload limit.
+fn validate_graph_size(field: &'static str, graph: &str) -> Result<(), Error> {
+    if graph.len() > *MAX_GRAPH_BYTES {
+        Err(Error::GraphTooLarge(field, *MAX_GRAPH_BYTES))
+    } else {
+        Ok(())
+    }
+}
+
+/// Runs graph parsing on the blocking pool, failing once it takes longer than
+/// `timeout`. The request is answered right away; the parse itself is not
+/// interrupted and finishes in the background.
+async fn parse_with_timeout<T, F>(timeout: Duration, parse: F) -> Result<T, Error>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, Error> + Send + 'static,
+{
+    match tokio::time::timeout(timeout, web::block(parse)).await {
+        Ok(result) => result.map_err(Error::BlockingError)?,
+        Err(_) => Err(Error::GraphParseTimeout(timeout.as_millis() as u64)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aggregation::{Aggregation, WeightBy};
+    use actix_web::{
+        dev::Service,
+        http::{header::ContentType, header::HeaderValue, StatusCode},
+        test,
+    };
+    use chrono::SecondsFormat;
+    use diesel::{Connection, PgConnection, RunQueryDsl};
+    use dotenvy::from_filename;
+    use oxigraph::io::RdfParser;
+    use serde_json::Value;
+    use std::{
+        collections::HashSet,
+        io::Read,
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+    use tokio::sync::RwLock;
+    use uuid::Uuid;
+
+    /// Tests that rely on stored data hold a read lock, so that resetting the
+    /// database with the write lock held cannot pull data out from under them.
+    static DATABASE: RwLock<()> = RwLock::const_new(());
+
+    fn load_test_env() {
+        match from_filename(".env.test") {
+            Ok(_) => println!("Successfully loaded .env.test"),
+            Err(err) => println!("Error loading .env.test: {}", err),
+        }
+    }
+
+    /// Loads `.env.test` and initializes the app with `state`.
+    async fn test_app(
+        state: AppState,
+    ) -> impl Service<
+        actix_http::Request,
+        Response = ServiceResponse<impl MessageBody>,
+        Error = actix_web::Error,
+    > {
+        load_test_env();
+        test::init_service(app(state)).await
+    }
+
+    fn post_body(dataset_uri: &str) -> Value {
+        let mut body = serde_json::from_str::<Value>(include_str!("../tests/post.json")).unwrap();
+        body["scores"]["dataset"]["id"] = Value::from(dataset_uri);
+        body
+    }
+
+    fn post_request(uuid: &str, body: Value) -> test::TestRequest {
+        test::TestRequest::post()
+            .insert_header(ContentType::json())
+            .insert_header(("X-API-KEY", "foo"))
+            .insert_header(("Origin", "http://localhost:8080"))
+            .set_json(body)
+            .uri(&format!("/api/assessments/{uuid}"))
+    }
+
+    async fn test_get_ok(path: &str) {
+        match from_filename(".env.test") {
+            Ok(_) => println!("Successfully loaded .env.test"),
+            Err(err) => println!("Error loading .env.test: {}", err),
+        }
+
+        let app = test::init_service(app(AppState::default())).await;
+        let req = test::TestRequest::get()
+            .insert_header(ContentType::plaintext())
+            .insert_header(("Origin", "http://localhost:8080"))
+            .uri(path)
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+    }
+
+    #[actix_web::test]
+    async fn test_ping() {
+        test_get_ok("/ping").await;
+    }
+
+    #[actix_web::test]
+    async fn test_ready() {
+        test_get_ok("/ready").await;
+    }
+
+    #[actix_web::test]
+    async fn test_replication_health() {
+        let app = test_app(AppState::default()).await;
+        let req = test::TestRequest::get()
+            .uri("/health/replication")
+            .to_request();
+        let health: models::ReplicationHealth = test::call_and_read_body_json(&app, req).await;
+
+        // The test database is a primary, so there is no lag to report.
+        assert_eq!(health.status, "not a replica");
+        assert!(!health.replica);
+        assert_eq!(health.lag_seconds, None);
+        assert_eq!(health.max_lag_seconds, 30.0);
+    }
+
+    #[actix_web::test]
+    async fn test_404() {
+        match from_filename(".env.test") {
+            Ok(_) => println!("Successfully loaded .env.test"),
+            Err(err) => println!("Error loading .env.test: {}", err),
+        }
+
+        let uuid = Uuid::parse_str("02f09a3f-1624-3b1d-1337-44eff7708208").unwrap();
+        let path = format!("/api/assessments/{}", uuid);
+
+        let app = test::init_service(app(AppState::default())).await;
+
+        let req = test::TestRequest::get()
+            .insert_header(ContentType::plaintext())
+            .insert_header(("Origin", "http://localhost:8080"))
+            .uri(&path)
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[actix_web::test]
+    async fn test_validation_errors() {
+        let app = test_app(AppState::default()).await;
+
+        let req = test::TestRequest::post()
+            .insert_header(ContentType::json())
+            .insert_header(("Origin", "http://localhost:8080"))
+            .set_json(
+                serde_json::from_str::<Value>(
+                    r#"{
+                    "datasets": [
+                        "not a uri",
+                        "https://dataset.foo",
+                        ""
+                    ]
+                }"#,
+                )
+                .unwrap(),
+            )
+            .uri("/api/scores")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let body: Value = test::read_body_json(resp).await;
+        assert_eq!(body["error"], "invalid request");
+        let errors = body["errors"].as_array().unwrap();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0]["field"], "datasets[0]");
+        assert_eq!(errors[1]["field"], "datasets[2]");
+        assert!(errors.iter().all(|e| e["message"].is_string()));
+    }
+
+    #[actix_web::test]
+    async fn test_malformed_requests() {
+        let recent_errors = RecentErrors::default();
+        let app = test_app(AppState {
+            recent_errors: recent_errors.clone(),
+            ..Default::default()
+        })
+        .await;
+
+        for (request, field) in [
+            (
+                test::TestRequest::post()
+                    .insert_header(ContentType::json())
+                    .set_payload(r#"{"datasets": "https://dataset.foo"}"#)
+                    .uri("/api/scores"),
+                "body",
+            ),
+            (
+                test::TestRequest::post()
+                    .insert_header(ContentType::json())
+                    .set_payload(r#"{"datasets": ["#)
+                    .uri("/api/scores"),
+                "body",
+            ),
+            (
+                test::TestRequest::post()
+                    .set_payload(vec![0xff, 0xfe])
+                    .uri("/api/scores"),
+                "body",
+            ),
+            (
+                test::TestRequest::get().uri("/api/assessments?limit=abc"),
+                "query",
+            ),
+        ] {
+            let resp = test::call_service(&app, request.to_request()).await;
+            assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+            let body: Value = test::read_body_json(resp).await;
+            assert!(body["error"].is_string());
+            assert_eq!(body["errors"][0]["field"], field);
+            assert!(body["errors"][0]["message"].is_string());
+        }
+        assert!(recent_errors.list().is_empty());
+    }
+
+    #[actix_web::test]
+    async fn test_changed_assessments() {
+        let _database = DATABASE.read().await;
+
+        let older = "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0001";
+        let newer = "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0002";
+
+        let app = test_app(AppState::default()).await;
+
+        let req = post_request(older, post_body("https://dataset.changed.older")).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        actix_web::rt::time::sleep(Duration::from_millis(10)).await;
+        let since = Utc::now().to_rfc3339_opts(SecondsFormat::Micros, true);
+        actix_web::rt::time::sleep(Duration::from_millis(10)).await;
+
+        let req = post_request(newer, post_body("https://dataset.changed.newer")).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        let req = test::TestRequest::get()
+            .uri(&format!(
+                "/api/assessments/changed?since={since}&limit=1000"
+            ))
+            .to_request();
+        let body: Vec<Value> = test::call_and_read_body_json(&app, req).await;
+        let ids: Vec<&str> = body.iter().map(|a| a["id"].as_str().unwrap()).collect();
+        assert!(ids.contains(&newer));
+        assert!(!ids.contains(&older));
+
+        let updated: Vec<&str> = body
+            .iter()
+            .map(|a| a["updated_at"].as_str().unwrap())
+            .collect();
+        assert!(updated
+            .iter()
+            .all(|t| DateTime::parse_from_rfc3339(t).unwrap()
+                > DateTime::parse_from_rfc3339(&since).unwrap()));
+
+        let req = test::TestRequest::get()
+            .uri("/api/assessments/changed?since=yesterday&limit=0")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let body: Value = test::read_body_json(resp).await;
+        assert_eq!(body["errors"][0]["field"], "since");
+        assert_eq!(body["errors"][1]["field"], "limit");
+
+        // Limits above MAX_PAGE_SIZE are clamped rather than refused.
+        let req = test::TestRequest::get()
+            .uri(&format!(
+                "/api/assessments/changed?since={since}&limit=1000000"
+            ))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[actix_web::test]
+    async fn test_scores_as_percent() {
+        let _database = DATABASE.read().await;
+
+        let app = test_app(AppState::default()).await;
+
+        let req = post_request(
+            "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0003",
+            post_body("https://dataset.percent"),
+        )
+        .to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        let req = test::TestRequest::post()
+            .set_json(serde_json::json!({ "datasets": ["https://dataset.percent"] }))
+            .uri("/api/scores?as_percent=true")
+            .to_request();
+        let body: Value = test::call_and_read_body_json(&app, req).await;
+
+        let aggregations = body["aggregations"].as_array().unwrap();
+        assert_eq!(aggregations.len(), 5);
+        for aggregate in aggregations {
+            let percent = aggregate["score_percent"].as_f64().unwrap();
+            assert!((0.0..=100.0).contains(&percent));
+            assert!(aggregate.get("score").is_none());
+        }
+        let accessibility = aggregations
+            .iter()
+            .find(|a| a["id"] == "https://data.norge.no/vocabulary/dcatno-mqa#accessibility")
+            .unwrap();
+        assert_eq!(accessibility["score_percent"], 70.0);
+        assert!(body["scores"]["https://dataset.percent"].is_object());
+
+        let dimension = &body["scores"]["https://dataset.percent"]["dataset"]["dimensions"][0];
+        assert_eq!(dimension["id"], accessibility["id"]);
+        assert_eq!(
+            (
+                &dimension["score"],
+                &dimension["max_score"],
+                &dimension["ratio"]
+            ),
+            (&Value::from(70), &Value::from(100), &Value::from(0.7))
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_warm_aggregates() {
+        let _database = DATABASE.read().await;
+
+        let catalog = "https://catalog.warm";
+        let app = test_app(AppState::default()).await;
+
+        let mut body = post_body("https://dataset.warm");
+        body["catalog_uri"] = Value::from(catalog);
+        let req = post_request("5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0004", body).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        let req = test::TestRequest::post()
+            .uri("/api/admin/warm-aggregates")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        let req = test::TestRequest::post()
+            .insert_header(("X-API-KEY", "foo"))
+            .uri("/api/admin/warm-aggregates")
+            .to_request();
+        let body: Value = test::call_and_read_body_json(&app, req).await;
+        assert!(body["groups"].as_i64().unwrap() >= 2);
+
+        let mut conn = PgPool::new().unwrap().get(QueryCount::default()).unwrap();
+        let cached = conn.cached_aggregates(catalog).unwrap();
+        assert_eq!(cached.len(), 5);
+        let accessibility = cached
+            .iter()
+            .find(|a| a.id == "https://data.norge.no/vocabulary/dcatno-mqa#accessibility")
+            .unwrap();
+        assert_eq!(
+            (accessibility.score, accessibility.max_score),
+            (70.0, 100.0)
+        );
+        assert!(!conn
+            .cached_aggregates(database::GLOBAL_SCOPE)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[actix_web::test]
+    async fn test_update_assessment_diff() {
+        let _database = DATABASE.read().await;
+
+        // Fresh ids, so the first post has no previous score even when an
+        // earlier run left one behind.
+        let uuid = Uuid::new_v4().to_string();
+        let uuid = uuid.as_str();
+        let dataset_uri = format!("https://dataset.diff/{uuid}");
+        let accessibility = "https://data.norge.no/vocabulary/dcatno-mqa#accessibility";
+        let app = test_app(AppState::default()).await;
+
+        let req = post_request(uuid, post_body(&dataset_uri))
+            .uri(&format!("/api/assessments/{uuid}?return=diff"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::ACCEPTED);
+        let body: Value = test::read_body_json(resp).await;
+        assert_eq!(body["dimensions"].as_array().unwrap().len(), 5);
+        assert!(body["dimensions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|dimension| dimension["previous_score"].is_null()));
+
+        let mut update = post_body(&dataset_uri);
+        for dimension in update["scores"]["dataset"]["dimensions"]
+            .as_array_mut()
+            .unwrap()
+        {
+            if dimension["id"] == accessibility {
+                dimension["score"] = Value::from(50);
+            }
+        }
+        let req = post_request(uuid, update)
+            .uri(&format!("/api/assessments/{uuid}?return=diff"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::ACCEPTED);
+        let body: Value = test::read_body_json(resp).await;
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "dimensions": [{ "id": accessibility, "previous_score": 70, "score": 50 }]
+            })
+        );
+
+        let req = post_request(uuid, post_body(&dataset_uri))
+            .uri(&format!("/api/assessments/{uuid}?return=everything"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn test_unknown_dimension_warning() {
+        let _database = DATABASE.read().await;
+
+        let uuid = "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0091";
+        let dataset_uri = "https://dataset.unknown.dimension";
+        let unknown = "https://dimension.unknown";
+        let app = test_app(AppState::default()).await;
+
+        let mut body = post_body(dataset_uri);
+        let dimensions = body["scores"]["dataset"]["dimensions"]
+            .as_array_mut()
+            .unwrap();
+        dimensions
+            .push(serde_json::json!({ "id": unknown, "metrics": [], "score": 1, "max_score": 2 }));
+        let field = format!("scores.dataset.dimensions[{}].id", dimensions.len() - 1);
+        let req = post_request(uuid, body).to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::ACCEPTED);
+        let body: models::ScorePostResponse = test::read_body_json(resp).await;
+        assert_eq!(body.dimensions, None);
+        let warnings = body.warnings.unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].field, field);
+        assert!(warnings[0].message.contains(unknown));
+
+        // The dimension is stored regardless.
+        let req = test::TestRequest::post()
+            .set_json(serde_json::json!({ "datasets": [dataset_uri] }))
+            .uri("/api/scores")
+            .to_request();
+        let body: Value = test::call_and_read_body_json(&app, req).await;
+        assert!(body["scores"][dataset_uri]["dataset"]["dimensions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|dimension| dimension["id"] == unknown && dimension["score"] == 1));
+    }
+
+    #[actix_web::test]
+    async fn test_unchanged_assessment_is_not_stored() {
+        let _database = DATABASE.read().await;
+
+        let uuid = "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0047";
+        let dataset_uri = "https://dataset.unchanged";
+        let accessibility = "https://data.norge.no/vocabulary/dcatno-mqa#accessibility";
+        let app = test_app(AppState::default()).await;
+        let history = || {
+            use diesel::{ExpressionMethods, QueryDsl};
+            use schema::assessment_history::dsl;
+            let mut conn = PgConnection::establish(&database::database_url().unwrap()).unwrap();
+            dsl::assessment_history
+                .filter(dsl::assessment_id.eq(uuid))
+                .count()
+                .get_result::<i64>(&mut conn)
+                .unwrap()
+        };
+
+        let req = post_request(uuid, post_body(dataset_uri)).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        let mut changed = post_body(dataset_uri);
+        for dimension in changed["scores"]["dataset"]["dimensions"]
+            .as_array_mut()
+            .unwrap()
+        {
+            if dimension["id"] == accessibility {
+                dimension["score"] = Value::from(50);
+            }
+        }
+        let req = post_request(uuid, changed.clone()).to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::ACCEPTED);
+        assert!(resp.headers().get(NOT_MODIFIED_HEADER).is_none());
+        let stored = history();
+
+        // The same graphs, only serialized differently: the JSON-LD nodes in
+        // reverse order, pretty-printed and with other blank node labels.
+        changed["turtle_assessment"] = Value::from(format!(
+            "# reformatted\n\n{}",
+            changed["turtle_assessment"].as_str().unwrap()
+        ));
+        let jsonld = changed["jsonld_assessment"]
+            .as_str()
+            .unwrap()
+            .replace("_:", "_:b");
+        let mut nodes: Vec<Value> = serde_json::from_str(&jsonld).unwrap();
+        nodes.reverse();
+        changed["jsonld_assessment"] = Value::from(serde_json::to_string_pretty(&nodes).unwrap());
+        let req = post_request(uuid, changed).to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers().get(NOT_MODIFIED_HEADER).unwrap(), "true");
+        assert_eq!(history(), stored);
+    }
+
+    #[actix_web::test]
+    async fn test_assessment_timestamps() {
+        let _database = DATABASE.read().await;
+
+        let uuid = "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0048";
+        let dataset_uri = "https://dataset.timestamps";
+        let app = test_app(AppState::default()).await;
+        let timestamps = || {
+            test::TestRequest::post()
+                .set_json(serde_json::json!({ "datasets": [dataset_uri] }))
+                .uri("/api/scores")
+                .to_request()
+        };
+        let parse =
+            |timestamp: &Value| DateTime::parse_from_rfc3339(timestamp.as_str().unwrap()).unwrap();
+
+        // The content differs from whatever an earlier run stored.
+        let mut body = post_body(dataset_uri);
+        body["catalog_uri"] = Value::from(format!("https://catalog.{}", Uuid::new_v4()));
+        let req = post_request(uuid, body).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+        let first: Value = test::call_and_read_body_json(&app, timestamps()).await;
+        let first = &first["timestamps"][dataset_uri];
+
+        let mut body = post_body(dataset_uri);
+        body["catalog_uri"] = Value::from(format!("https://catalog.{}", Uuid::new_v4()));
+        let req = post_request(uuid, body).to_request();
+        assert_eq!(
+            test::call_service(&app, req).await.status(),
+            StatusCode::ACCEPTED
+        );
+        let second: Value = test::call_and_read_body_json(&app, timestamps()).await;
+        let second = &second["timestamps"][dataset_uri];
+
+        assert_eq!(second["created_at"], first["created_at"]);
+        assert!(parse(&second["updated_at"]) > parse(&first["updated_at"]));
+        assert!(parse(&second["created_at"]) <= parse(&first["updated_at"]));
+    }
+
+    #[actix_web::test]
+    async fn test_query_count() {
+        load_test_env();
+        let _database = DATABASE.read().await;
+
+        let datasets = vec!["https://dataset.foo".to_string()];
+        let queries = QueryCount::default();
+        let mut conn = PgPool::new().unwrap().get(queries.clone()).unwrap();
+        assert_eq!(queries.get(), 0);
+
+        conn.test_connection().unwrap();
+        assert_eq!(queries.get(), 1);
+
+        conn.json_scores(&datasets).unwrap();
+        conn.dimension_aggregates(&datasets, &[], Aggregation::Mean, WeightBy::Datasets)
+            .unwrap();
+        assert_eq!(queries.get(), 3);
+
+        let dataset_uri = "https://dataset.query.count".to_string();
+        let assessment = DatasetAssessment {
+            id: "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0045".to_string(),
+            dataset_uri: dataset_uri.clone(),
+            turtle_assessment: Some("".to_string()),
+            jsonld_assessment: Some("[]".to_string()),
+            json_score: "{}".to_string(),
+            catalog_uri: None,
+            publisher_uri: None,
+            content_hash: None,
+            distribution_count: 0,
+        };
+        let dimension = Dimension {
+            dataset_uri,
+            id: "https://dimension.query.count".to_string(),
+            score: 1,
+            max_score: 2,
+        };
+        conn.store_assessment_with_dimensions(assessment, &[dimension], GraphStorage::Inline)
+            .unwrap();
+        assert_eq!(queries.get(), 7);
+    }
+
+    #[actix_web::test]
+    async fn test_store_assessment_is_atomic() {
+        let _database = DATABASE.read().await;
+
+        let uuid = "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0046";
+        let dataset_uri = "https://dataset.atomic";
+        let app = test_app(AppState::default()).await;
+        let req = post_request(uuid, post_body(dataset_uri)).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        let mut conn = PgPool::new().unwrap().get(QueryCount::default()).unwrap();
+        let datasets = vec![dataset_uri.to_string()];
+        let before = conn.json_scores(&datasets).unwrap();
+        let dimensions_before = conn
+            .dimension_aggregates(&datasets, &[], Aggregation::Mean, WeightBy::Datasets)
+            .unwrap();
+        assert_eq!(dimensions_before.len(), 5);
+
+        // The second dimension belongs to a dataset without an assessment, which
+        // violates the foreign key after the first one has been written.
+        let dimension = |dataset_uri: &str| Dimension {
+            dataset_uri: dataset_uri.to_string(),
+            id: "https://dimension.atomic".to_string(),
+            score: 1,
+            max_score: 1,
+        };
+        let assessment = DatasetAssessment {
+            id: uuid.to_string(),
+            dataset_uri: dataset_uri.to_string(),
+            turtle_assessment: Some("".to_string()),
+            jsonld_assessment: Some("[]".to_string()),
+            json_score: "{}".to_string(),
+            catalog_uri: None,
+            publisher_uri: None,
+            content_hash: None,
+            distribution_count: 0,
+        };
+        assert!(conn
+            .store_assessment_with_dimensions(
+                assessment,
+                &[
+                    dimension(dataset_uri),
+                    dimension("https://dataset.atomic.missing")
+                ],
+                GraphStorage::Inline,
+            )
+            .is_err());
+
+        assert_eq!(conn.json_scores(&datasets).unwrap(), before);
+        let dimensions_after = conn
+            .dimension_aggregates(&datasets, &[], Aggregation::Mean, WeightBy::Datasets)
+            .unwrap();
+        assert_eq!(
+            dimensions_after
+                .iter()
+                .map(|d| (d.id.clone(), d.score, d.max_score))
+                .collect::<Vec<_>>(),
+            dimensions_before
+                .iter()
+                .map(|d| (d.id.clone(), d.score, d.max_score))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_store_many_dimensions() {
+        load_test_env();
+        let _database = DATABASE.read().await;
+
+        let dataset_uri = "https://dataset.many.dimensions".to_string();
+        let assessment = || DatasetAssessment {
+            id: "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0054".to_string(),
+            dataset_uri: dataset_uri.clone(),
+            turtle_assessment: Some("".to_string()),
+            jsonld_assessment: Some("[]".to_string()),
+            json_score: "{}".to_string(),
+            catalog_uri: None,
+            publisher_uri: None,
+            content_hash: None,
+            distribution_count: 0,
+        };
+        let dimension = |i: i32, score: i32| Dimension {
+            dataset_uri: dataset_uri.clone(),
+            id: format!("https://dimension.many/{i:03}"),
+            score,
+            max_score: 200,
+        };
+        let queries = QueryCount::default();
+        let mut conn = PgPool::new().unwrap().get(queries.clone()).unwrap();
+
+        // Stored twice, so the second run updates every row. The repeated first
+        // dimension keeps its last score, as separate upserts would.
+        for offset in [0, 1] {
+            let mut dimensions: Vec<Dimension> =
+                (0..200).map(|i| dimension(i, i + offset)).collect();
+            dimensions.insert(0, dimension(0, -1));
+            let before = queries.get();
+            conn.store_assessment_with_dimensions(assessment(), &dimensions, GraphStorage::Inline)
+                .unwrap();
+            assert_eq!(queries.get() - before, 4);
+
+            let stored = conn
+                .dimension_aggregates(
+                    std::slice::from_ref(&dataset_uri),
+                    &[],
+                    Aggregation::Mean,
+                    WeightBy::Datasets,
+                )
+                .unwrap();
+            let mut stored: Vec<_> = stored
+                .iter()
+                .map(|d| (d.id.clone(), d.score as i64, d.max_score as i64))
+                .collect();
+            stored.sort();
+            assert_eq!(
+                stored,
+                (0..200)
+                    .map(|i| (
+                        format!("https://dimension.many/{i:03}"),
+                        (i + offset) as i64,
+                        200
+                    ))
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_assessment_uri() {
+        let _database = DATABASE.read().await;
+
+        let uuid = "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0063";
+        let dataset_uri = "https://dataset.uri.check";
+        let app = test_app(AppState::default()).await;
+        let req = post_request(uuid, post_body(dataset_uri)).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        let uri = |uuid: &str| {
+            test::TestRequest::get()
+                .uri(&format!("/api/assessments/{uuid}/uri"))
+                .to_request()
+        };
+        let expected = serde_json::json!({
+            "id": uuid,
+            "dataset_uri": dataset_uri,
+            "assessment_count": 1,
+            "duplicate_uri": false,
+        });
+        let body: Value = test::call_and_read_body_json(&app, uri(uuid)).await;
+        assert_eq!(body, expected);
+
+        // The dataset uri is unique, so a second id for it is refused and the
+        // first one stays the only assessment of the uri.
+        let duplicate = "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0064";
+        let req = post_request(duplicate, post_body(dataset_uri)).to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+        let body: Value = test::read_body_json(resp).await;
+        assert_eq!(body["errors"][0]["field"], "scores.dataset.id");
+        let resp = test::call_service(&app, uri(duplicate)).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        let body: Value = test::call_and_read_body_json(&app, uri(uuid)).await;
+        assert_eq!(body, expected);
+
+        let resp = test::call_service(&app, uri("not-a-uuid")).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn test_post_malformed_graphs() {
+        let _database = DATABASE.read().await;
+
+        let app = test_app(AppState::default()).await;
+
+        for (uuid, field, graph) in [
+            (
+                "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0074",
+                "turtle_assessment",
+                "<https://dataset.malformed.turtle> is not turtle",
+            ),
+            (
+                "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0075",
+                "jsonld_assessment",
+                "[{\"@id\": ",
+            ),
+        ] {
+            let mut body = post_body(&format!("https://dataset.malformed.{field}"));
+            body[field] = Value::from(graph);
+            let resp = test::call_service(&app, post_request(uuid, body).to_request()).await;
+            assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+            let body: Value = test::read_body_json(resp).await;
+            assert!(body["error"].as_str().unwrap().contains(field));
+
+            let req = test::TestRequest::get()
+                .uri(&format!("/api/assessments/{uuid}"))
+                .to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_negotiate() {
+        let negotiated = |accept: &str| {
+            let req = test::TestRequest::default()
+                .insert_header((header::ACCEPT, accept))
+                .to_http_request();
+            let accept = <header::Accept as header::Header>::parse(&req).unwrap();
+            negotiate(
+                &accept,
+                &[TEXT_TURTLE, APPLICATION_TRIG, APPLICATION_LD_JSON],
+            )
+            .ok()
+        };
+        assert_eq!(negotiated(""), Some(TEXT_TURTLE));
+        assert_eq!(negotiated("*/*"), Some(TEXT_TURTLE));
+        assert_eq!(negotiated("application/*"), Some(APPLICATION_TRIG));
+        assert_eq!(negotiated("application/ld+json"), Some(APPLICATION_LD_JSON));
+        assert_eq!(
+            negotiated("application/ld+json, application/trig"),
+            Some(APPLICATION_TRIG)
+        );
+        assert_eq!(
+            negotiated("application/ld+json;q=0.5, text/turtle"),
+            Some(TEXT_TURTLE)
+        );
+        assert_eq!(
+            negotiated("text/turtle;q=0.2, application/ld+json;q=0.9"),
+            Some(APPLICATION_LD_JSON)
+        );
+        assert_eq!(
+            negotiated("application/trig;q=0, application/ld+json;q=0.1"),
+            Some(APPLICATION_LD_JSON)
+        );
+        assert_eq!(negotiated("application/json, */*;q=0.1"), Some(TEXT_TURTLE));
+        assert_eq!(
+            negotiated("text/turtle;q=0, */*;q=0.5"),
+            Some(APPLICATION_TRIG)
+        );
+        assert_eq!(negotiated("application/json"), None);
+        assert_eq!(negotiated("application/ld+json;q=0"), None);
+
+        let app = test_app(AppState::default()).await;
+        let req = test::TestRequest::get()
+            .insert_header((header::ACCEPT, "application/json"))
+            .uri(&format!("/api/assessments/{}", Uuid::new_v4()))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_ACCEPTABLE);
+    }
+
+    #[actix_web::test]
+    async fn test_assessments_head_and_empty() {
+        let _database = DATABASE.read().await;
+
+        let dataset_uri = "https://dataset.head";
+        let app = test_app(AppState::default()).await;
+        let req = post_request(
+            "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0076",
+            post_body(dataset_uri),
+        )
+        .to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        // HEAD answers with the headers of the post, without a body.
+        let req = test::TestRequest::default()
+            .method(Method::HEAD)
+            .insert_header((header::ACCEPT, APPLICATION_TRIG))
+            .set_json(serde_json::json!({ "datasets": [dataset_uri] }))
+            .uri("/api/assessments")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers().get(header::CONTENT_TYPE).unwrap(),
+            APPLICATION_TRIG
+        );
+        assert!(test::read_body(resp).await.is_empty());
+
+        let req = test::TestRequest::default()
+            .method(Method::HEAD)
+            .uri("/api/assessments")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers().get(header::CONTENT_TYPE).unwrap(),
+            TEXT_TURTLE
+        );
+
+        // An empty body, like an empty datasets array, yields an empty graph.
+        let req = test::TestRequest::post()
+            .uri("/api/assessments")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(test::read_body(resp).await.is_empty());
+
+        let req = test::TestRequest::post()
+            .insert_header((header::ACCEPT, APPLICATION_LD_JSON))
+            .set_json(serde_json::json!({ "datasets": [] }))
+            .uri("/api/assessments")
+            .to_request();
+        let body: Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["@graph"], serde_json::json!([]));
+    }
+
+    #[actix_web::test]
+    async fn test_assessment_rdf_xml() {
+        let _database = DATABASE.read().await;
+
+        let uuid = "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0080";
+        let app = test_app(AppState::default()).await;
+        let body = post_body("https://dataset.rdfxml");
+        let turtle = body["turtle_assessment"].as_str().unwrap().to_string();
+        let req = post_request(uuid, body).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        let req = test::TestRequest::get()
+            .insert_header((header::ACCEPT, APPLICATION_RDF_XML))
+            .uri(&format!("/api/assessments/{uuid}"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers().get(header::CONTENT_TYPE).unwrap(),
+            APPLICATION_RDF_XML
+        );
+        let body = test::read_body(resp).await;
+        let xml = std::str::from_utf8(&body).unwrap();
+        assert!(xml.starts_with("<?xml"), "{xml}");
+        assert!(xml.contains("rdf:about=\"https://dataset.assessment.foo\""));
+
+        // The document is well-formed and holds every triple of the graph.
+        let triples = RdfParser::from_format(RdfFormat::RdfXml)
+            .for_slice(xml.as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let expected = RdfParser::from_format(RdfFormat::Turtle)
+            .for_slice(turtle.as_bytes())
+            .count();
+        assert_eq!(triples.len(), expected);
+    }
+
+    #[actix_web::test]
+    async fn test_separate_graph_storage() {
+        let _database = DATABASE.read().await;
+
+        let uuid = "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0086";
+        let dataset_uri = "https://dataset.separate.graphs";
+        let app = test_app(AppState::default()).await;
+
+        let body = post_body(dataset_uri);
+        let turtle = body["turtle_assessment"].as_str().unwrap().to_string();
+        let jsonld = body["jsonld_assessment"].as_str().unwrap().to_string();
+        let dimensions: Vec<Dimension> = body["scores"]["dataset"]["dimensions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|dimension| Dimension {
+                dataset_uri: dataset_uri.to_string(),
+                id: dimension["id"].as_str().unwrap().to_string(),
+                score: dimension["score"].as_i64().unwrap() as i32,
+                max_score: dimension["max_score"].as_i64().unwrap() as i32,
+            })
+            .collect();
+        let assessment = DatasetAssessment {
+            id: uuid.to_string(),
+            dataset_uri: dataset_uri.to_string(),
+            turtle_assessment: Some(turtle.clone()),
+            jsonld_assessment: Some(jsonld.clone()),
+            json_score: body["scores"].to_string(),
+            catalog_uri: None,
+            publisher_uri: None,
+            content_hash: None,
+            distribution_count: 2,
+        };
+        let mut conn = PgPool::new().unwrap().get(QueryCount::default()).unwrap();
+        conn.store_assessment_with_dimensions(assessment, &dimensions, GraphStorage::Separate)
+            .unwrap();
+
+        let inline_graphs = || {
+            use diesel::QueryDsl;
+            use schema::dataset_assessments::dsl;
+            let mut conn = PgConnection::establish(&database::database_url().unwrap()).unwrap();
+            dsl::dataset_assessments
+                .find(uuid)
+                .select((dsl::turtle_assessment, dsl::jsonld_assessment))
+                .first::<(Option<String>, Option<String>)>(&mut conn)
+                .unwrap()
+        };
+        assert_eq!(inline_graphs(), (None, None));
+
+        let graph = |accept: &'static str| {
+            test::TestRequest::get()
+                .insert_header((header::ACCEPT, accept))
+                .uri(&format!("/api/assessments/{uuid}"))
+                .to_request()
+        };
+        let body = test::call_and_read_body(&app, graph("text/turtle")).await;
+        assert_eq!(std::str::from_utf8(&body).unwrap(), turtle);
+        let body = test::call_and_read_body(&app, graph(APPLICATION_LD_JSON)).await;
+        assert_eq!(std::str::from_utf8(&body).unwrap(), jsonld);
+
+        let req = test::TestRequest::post()
+            .set_json(serde_json::json!({ "datasets": [dataset_uri] }))
+            .uri("/api/scores")
+            .to_request();
+        let body: Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(
+            body["scores"][dataset_uri]["dataset"]["dimensions"]
+                .as_array()
+                .unwrap()
+                .len(),
+            dimensions.len()
+        );
+
+        // Graphs written inline take precedence over those left in the separate table.
+        let updated = "<https://dataset.separate.graphs> <https://b> \"inline\" .\n";
+        assert!(conn
+            .update_graphs(
+                Uuid::parse_str(uuid).unwrap(),
+                updated,
+                &jsonld,
+                GraphStorage::Inline,
+            )
+            .unwrap());
+        assert_eq!(
+            inline_graphs(),
+            (Some(updated.to_string()), Some(jsonld.clone()))
+        );
+        let body = test::call_and_read_body(&app, graph("text/turtle")).await;
+        assert_eq!(std::str::from_utf8(&body).unwrap(), updated);
+    }
+
+    #[actix_web::test]
+    async fn test_assessment_ntriples() {
+        let _database = DATABASE.read().await;
+
+        let uuid = "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0077";
+        let app = test_app(AppState::default()).await;
+        let body = post_body("https://dataset.ntriples");
+        let turtle = body["turtle_assessment"].as_str().unwrap().to_string();
+        let req = post_request(uuid, body).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        let req = test::TestRequest::get()
+            .insert_header((header::ACCEPT, APPLICATION_N_TRIPLES))
+            .uri(&format!("/api/assessments/{uuid}"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers().get(header::CONTENT_TYPE).unwrap(),
+            APPLICATION_N_TRIPLES
+        );
+        let body = test::read_body(resp).await;
+        let ntriples = std::str::from_utf8(&body).unwrap();
+
+        let lines: Vec<&str> = ntriples.lines().collect();
+        let triples = RdfParser::from_format(RdfFormat::Turtle)
+            .for_slice(turtle.as_bytes())
+            .count();
+        assert_eq!(lines.len(), triples);
+        for line in &lines {
+            assert!(line.ends_with(" ."), "{line}");
+            let parsed = RdfParser::from_format(RdfFormat::NTriples)
+                .for_slice(line.as_bytes())
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            assert_eq!(parsed.len(), 1);
+        }
+        let mut sorted = lines.clone();
+        sorted.sort();
+        assert_eq!(lines, sorted);
+
+        // Without an Accept header the graph is served as Turtle.
+        let req = test::TestRequest::get()
+            .uri(&format!("/api/assessments/{uuid}"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(
+            resp.headers().get(header::CONTENT_TYPE).unwrap(),
+            TEXT_TURTLE
+        );
+    }
+
+    /// Log output written to a shared buffer.
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_log_redacts_headers() {
+        let logs = CapturedLogs::default();
+        let writer = logs.clone();
+        let _subscriber = tracing::subscriber::set_default(
+            tracing_subscriber::fmt()
+                .with_max_level(tracing::Level::DEBUG)
+                .with_writer(move || writer.clone())
+                .finish(),
+        );
+
+        let app = test_app(AppState::default()).await;
+        let req = test::TestRequest::get()
+            .insert_header(("X-API-KEY", API_KEY.as_str()))
+            .insert_header(("Authorization", "Bearer redact-me"))
+            .insert_header(("Origin", "http://localhost:8080"))
+            .uri("/ping")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+
+        let output = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        assert!(output.contains("x-api-key: ***"), "{output}");
+        assert!(output.contains("authorization: ***"), "{output}");
+        assert!(output.contains("origin: http://localhost:8080"), "{output}");
+        assert!(!output.contains(API_KEY.as_str()), "{output}");
+        assert!(!output.contains("redact-me"), "{output}");
+    }
+
+    #[actix_web::test]
+    async fn test_request_id() {
+        // Formatted as in `main`.
+        let logs = CapturedLogs::default();
+        let writer = logs.clone();
+        let _subscriber = tracing::subscriber::set_default(
+            tracing_subscriber::fmt()
+                .json()
+                .with_max_level(tracing::Level::DEBUG)
+                .with_current_span(false)
+                .with_writer(move || writer.clone())
+                .finish(),
+        );
+
+        let app = test_app(AppState::default()).await;
+        let req = test::TestRequest::get()
+            .insert_header((REQUEST_ID_HEADER, "pipeline-4711"))
+            .uri("/ready")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(
+            resp.headers().get(REQUEST_ID_HEADER).unwrap(),
+            "pipeline-4711"
+        );
+
+        let output = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        let completed: Value = output
+            .lines()
+            .map(|line| serde_json::from_str::<Value>(line).unwrap())
+            .find(|line| line["fields"]["message"] == "request completed")
+            .unwrap();
+        assert_eq!(completed["spans"][0]["request_id"], "pipeline-4711");
+
+        // Without one, an id is generated.
+        let req = test::TestRequest::get().uri("/ready").to_request();
+        let resp = test::call_service(&app, req).await;
+        let generated = resp.headers().get(REQUEST_ID_HEADER).unwrap();
+        assert!(Uuid::parse_str(generated.to_str().unwrap()).is_ok());
+    }
+
+    #[actix_web::test]
+    async fn test_recent_errors() {
+        let recent_errors = RecentErrors::default();
+        let app = test_app(AppState {
+            recent_errors: recent_errors.clone(),
+            ..Default::default()
+        })
+        .await;
+
+        // Postgres refuses NUL characters in text, which fails with 500.
+        let req = test::TestRequest::post()
+            .insert_header((REQUEST_ID_HEADER, "request-500"))
+            .insert_header(("X-API-KEY", API_KEY.as_str()))
+            .set_json(serde_json::json!({ "key": "note", "value": "\u{0}" }))
+            .uri("/api/assessments/5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d00a5/annotations")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(
+            resp.headers().get(REQUEST_ID_HEADER).unwrap(),
+            "request-500"
+        );
+
+        // Client errors are not recorded.
+        let req = test::TestRequest::get()
+            .uri("/api/assessments/invalid")
+            .to_request();
+        assert_eq!(
+            test::call_service(&app, req).await.status(),
+            StatusCode::BAD_REQUEST
+        );
+
+        let req = test::TestRequest::get()
+            .uri("/api/admin/recent-errors")
+            .to_request();
+        assert_eq!(
+            test::call_service(&app, req).await.status(),
+            StatusCode::UNAUTHORIZED
+        );
+
+        let req = test::TestRequest::get()
+            .insert_header(("X-API-KEY", API_KEY.as_str()))
+            .uri("/api/admin/recent-errors")
+            .to_request();
+        let errors: Vec<models::RecentError> = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].request_id, "request-500");
+        assert_eq!(errors[0].method, "POST");
+        assert_eq!(
+            errors[0].path,
+            "/api/assessments/5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d00a5/annotations"
+        );
+        assert_eq!(errors[0].status, 500);
+        assert!(!errors[0].error.contains(API_KEY.as_str()));
+        assert_eq!(errors, recent_errors.list());
+    }
+
+    #[actix_web::test]
+    async fn test_pool_state() {
+        let _database = DATABASE.read().await;
+
+        let app = test_app(AppState::default()).await;
+
+        let req = test::TestRequest::get().uri("/api/debug/pool").to_request();
+        assert_eq!(
+            test::call_service(&app, req).await.status(),
+            StatusCode::UNAUTHORIZED
+        );
+
+        let req = test::TestRequest::get()
+            .insert_header(("X-API-KEY", API_KEY.as_str()))
+            .uri("/api/debug/pool")
+            .to_request();
+        let body: Value = test::call_and_read_body_json(&app, req).await;
+        for field in [
+            "connections",
+            "idle_connections",
+            "in_use_connections",
+            "max_size",
+        ] {
+            assert!(body[field].is_u64(), "{field} in {body}");
+        }
+        let state: models::PoolState = serde_json::from_value(body).unwrap();
+        assert_eq!(
+            state.in_use_connections,
+            state.connections - state.idle_connections
+        );
+        assert!(state.connections <= state.max_size);
+    }
+
+    #[actix_web::test]
+    async fn test_graph_version() {
+        let _database = DATABASE.read().await;
+
+        let uuid = "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0006";
+        let app = test_app(AppState::default()).await;
+
+        let mut older = post_body("https://dataset.history");
+        older["turtle_assessment"] = Value::from("<https://a> <https://b> \"older\" .\n");
+        let req = post_request(uuid, older).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        actix_web::rt::time::sleep(Duration::from_millis(10)).await;
+        let version = Utc::now().to_rfc3339_opts(SecondsFormat::Micros, true);
+        actix_web::rt::time::sleep(Duration::from_millis(10)).await;
+
+        let mut newer = post_body("https://dataset.history");
+        newer["turtle_assessment"] = Value::from("<https://a> <https://b> \"newer\" .\n");
+        let req = post_request(uuid, newer).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/api/assessments/{uuid}/graph?version={version}"))
+            .to_request();
+        let bytes = test::call_and_read_body(&app, req).await;
+        assert_eq!(bytes, "<https://a> <https://b> \"older\" .\n");
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/api/assessments/{uuid}/graph"))
+            .to_request();
+        let bytes = test::call_and_read_body(&app, req).await;
+        assert_eq!(bytes, "<https://a> <https://b> \"newer\" .\n");
+
+        let req = test::TestRequest::get()
+            .uri(&format!(
+                "/api/assessments/{uuid}/graph?version=2000-01-01T00:00:00Z"
+            ))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[actix_web::test]
+    async fn test_graph_dimension_filter() {
+        let _database = DATABASE.read().await;
+
+        let uuid = "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0017";
+        let app = test_app(AppState::default()).await;
+
+        let mut body = post_body("https://dataset.filtered");
+        body["turtle_assessment"] = Value::from(
+            "<https://assessment> <https://data.norge.no/vocabulary/dcatno-mqa#containsQualityMeasurement> _:a, _:b .\n\
+             _:a <http://www.w3.org/ns/dqv#isMeasurementOf> <https://metric.a> .\n\
+             _:b <http://www.w3.org/ns/dqv#isMeasurementOf> <https://metric.b> .\n\
+             <https://metric.a> <http://www.w3.org/ns/dqv#inDimension> <https://dimension#a> .\n\
+             <https://metric.b> <http://www.w3.org/ns/dqv#inDimension> <https://dimension#b> .\n",
+        );
+        let req = post_request(uuid, body).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        let req = test::TestRequest::get()
+            .uri(&format!(
+                "/api/assessments/{uuid}?dimension=https%3A%2F%2Fdimension%23a"
+            ))
+            .to_request();
+        let bytes = test::call_and_read_body(&app, req).await;
+        let graph = from_utf8(&bytes).unwrap();
+        assert!(graph.contains("https://metric.a"));
+        assert!(!graph.contains("https://metric.b"));
+        assert!(!graph.contains("https://dimension#b"));
+        assert!(graph.contains("https://assessment"));
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/api/assessments/{uuid}?dimension=not%20an%20iri"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn test_assessment_distributions() {
+        let _database = DATABASE.read().await;
+
+        let uuid = "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d001d";
+        let app = test_app(AppState::default()).await;
+
+        let req = post_request(uuid, post_body("https://dataset.distributions")).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/api/assessments/{uuid}/distributions"))
+            .to_request();
+        let distributions: Vec<models::Score> = test::call_and_read_body_json(&app, req).await;
+        let ids: Vec<&str> = distributions.iter().map(|d| d.id.as_str()).collect();
+        assert_eq!(ids, ["https://distribution.a", "https://distribution.b"]);
+        assert!(distributions.iter().all(|d| !d.dimensions.is_empty()));
+
+        let req = test::TestRequest::get()
+            .uri("/api/assessments/5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1dffff/distributions")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[actix_web::test]
+    async fn test_assessment_score() {
+        let _database = DATABASE.read().await;
+
+        let uuid = "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0099";
+        let app = test_app(AppState::default()).await;
+
+        let body = post_body("https://dataset.assessment.score");
+        let posted: models::DatasetScore = serde_json::from_value(body["scores"].clone()).unwrap();
+        let req = post_request(uuid, body).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/api/assessments/{uuid}/score"))
+            .to_request();
+        let score: Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(score["dataset"]["dimensions"][0]["ratio"], 0.7);
+        assert_eq!(
+            serde_json::from_value::<models::DatasetScore>(score).unwrap(),
+            posted
+        );
+
+        let req = test::TestRequest::get()
+            .uri("/api/assessments/5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1dffff/score")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        let app = test_app(AppState {
+            read_access: ReadAccess {
+                require_api_key: true,
+            },
+            ..Default::default()
+        })
+        .await;
+        let req = test::TestRequest::get()
+            .uri(&format!("/api/assessments/{uuid}/score"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[actix_web::test]
+    async fn test_assessment_graph_etag() {
+        let _database = DATABASE.read().await;
+
+        let uuid = "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d00a3";
+        let app = test_app(AppState::default()).await;
+
+        let req = post_request(uuid, post_body("https://dataset.assessment.etag")).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/api/assessments/{uuid}"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let etag = resp.headers().get(header::ETAG).unwrap().clone();
+        assert!(etag.to_str().unwrap().starts_with("W/\""));
+        // Cors adds its own headers to Vary.
+        assert!(resp
+            .headers()
+            .get(header::VARY)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .split(", ")
+            .any(|v| v == "Accept"));
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/api/assessments/{uuid}"))
+            .insert_header((header::IF_NONE_MATCH, etag.clone()))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(resp.headers().get(header::ETAG), Some(&etag));
+        assert!(resp
+            .headers()
+            .get(header::VARY)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .split(", ")
+            .any(|v| v == "Accept"));
+        assert!(test::read_body(resp).await.is_empty());
+
+        // A compressed body keeps the tag, which weak comparison still matches.
+        let req = test::TestRequest::get()
+            .uri(&format!("/api/assessments/{uuid}"))
+            .insert_header((header::ACCEPT_ENCODING, "gzip"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(
+            resp.headers().get(header::CONTENT_ENCODING).unwrap(),
+            "gzip"
+        );
+        assert_eq!(resp.headers().get(header::ETAG), Some(&etag));
+
+        // Other representations have tags of their own.
+        let req = test::TestRequest::get()
+            .uri(&format!("/api/assessments/{uuid}"))
+            .insert_header((header::ACCEPT, "application/ld+json"))
+            .insert_header((header::IF_NONE_MATCH, etag.clone()))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_ne!(resp.headers().get(header::ETAG), Some(&etag));
+    }
+
+    #[actix_web::test]
+    async fn test_assessment_graph_last_modified() {
+        let _database = DATABASE.read().await;
+
+        let uuid = "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d00a4";
+        let app = test_app(AppState::default()).await;
+
+        let req =
+            post_request(uuid, post_body("https://dataset.assessment.last-modified")).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/api/assessments/{uuid}"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let last_modified = resp.headers().get(header::LAST_MODIFIED).unwrap().clone();
+
+        // At the stored timestamp.
+        let req = test::TestRequest::get()
+            .uri(&format!("/api/assessments/{uuid}"))
+            .insert_header((header::IF_MODIFIED_SINCE, last_modified.clone()))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(
+            resp.headers().get(header::LAST_MODIFIED),
+            Some(&last_modified)
+        );
+        assert!(test::read_body(resp).await.is_empty());
+
+        // After it.
+        let later = SystemTime::now() + Duration::from_secs(3600);
+        let req = test::TestRequest::get()
+            .uri(&format!("/api/assessments/{uuid}"))
+            .insert_header(header::IfModifiedSince(later.into()))
+            .to_request();
+        assert_eq!(
+            test::call_service(&app, req).await.status(),
+            StatusCode::NOT_MODIFIED
+        );
+
+        // Before it.
+        let req = test::TestRequest::get()
+            .uri(&format!("/api/assessments/{uuid}"))
+            .insert_header(header::IfModifiedSince(SystemTime::UNIX_EPOCH.into()))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::OK);
+
+        // A stale entity tag wins over a current date.
+        let req = test::TestRequest::get()
+            .uri(&format!("/api/assessments/{uuid}"))
+            .insert_header((header::IF_NONE_MATCH, "\"stale\""))
+            .insert_header((header::IF_MODIFIED_SINCE, last_modified))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::OK);
+    }
+
+    #[actix_web::test]
+    async fn test_sparql_update() {
+        let _database = DATABASE.read().await;
+
+        let uuid = "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d001e";
+        let app = test_app(AppState::default()).await;
+
+        let req = post_request(uuid, post_body("https://dataset.patched")).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        let update = |body: &'static str| {
+            test::TestRequest::post()
+                .insert_header((header::CONTENT_TYPE, "application/sparql-update"))
+                .insert_header(("X-API-KEY", "foo"))
+                .set_payload(body)
+                .uri(&format!("/api/assessments/{uuid}/sparql-update"))
+                .to_request()
+        };
+
+        let req = update(
+            "INSERT DATA { <https://dataset.patched> <https://example.com/note> \"patched\" }",
+        );
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::OK);
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/api/assessments/{uuid}"))
+            .to_request();
+        let bytes = test::call_and_read_body(&app, req).await;
+        let turtle = from_utf8(&bytes).unwrap();
+        assert!(turtle.contains("<https://example.com/note> \"patched\""));
+        assert!(turtle.contains("dcatno-mqa#containsQualityMeasurement"));
+
+        let req = update("DELETE WHERE { ?s ?p ?o }");
+        assert_eq!(
+            test::call_service(&app, req).await.status(),
+            StatusCode::BAD_REQUEST
+        );
+
+        let req = test::TestRequest::post()
+            .set_payload("INSERT DATA { <https://a> <https://b> <https://c> }")
+            .uri(&format!("/api/assessments/{uuid}/sparql-update"))
+            .to_request();
+        assert_eq!(
+            test::call_service(&app, req).await.status(),
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_localized_errors() {
+        let _database = DATABASE.read().await;
+
+        let app = test_app(AppState::default()).await;
+        let uuid = Uuid::new_v4();
+
+        let req = test::TestRequest::get()
+            .insert_header((header::ACCEPT_LANGUAGE, "nb-NO, en;q=0.5"))
+            .uri(&format!("/api/assessments/{uuid}/distributions"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        let body: Value = test::read_body_json(resp).await;
+        assert_eq!(
+            body["message"],
+            format!("datasett med FDK ID '{uuid}' finnes ikke")
+        );
+
+        let req = test::TestRequest::get()
+            .insert_header((header::ACCEPT_LANGUAGE, "de, en;q=0.5, nb;q=0.3"))
+            .uri(&format!("/api/assessments/{uuid}/distributions"))
+            .to_request();
+        let body: Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(
+            body["message"],
+            format!("dataset with FDK ID '{uuid}' does not exist")
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_assessments_trig() {
+        let _database = DATABASE.read().await;
+
+        let app = test_app(AppState::default()).await;
+
+        for (uuid, dataset) in [
+            (
+                "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0023",
+                "https://dataset.trig.a",
+            ),
+            (
+                "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0024",
+                "https://dataset.trig.b",
+            ),
+        ] {
+            let req = post_request(uuid, post_body(dataset)).to_request();
+            assert!(test::call_service(&app, req).await.status().is_success());
+        }
+
+        let req = test::TestRequest::post()
+            .insert_header((header::ACCEPT, "application/trig"))
+            .set_json(serde_json::json!({
+                "datasets": ["https://dataset.trig.a", "https://dataset.trig.b"]
+            }))
+            .uri("/api/assessments")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(
+            resp.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/trig"
+        );
+        let bytes = test::read_body(resp).await;
+
+        let graphs: HashSet<String> = RdfParser::from_format(RdfFormat::TriG)
+            .for_slice(&bytes)
+            .map(|quad| quad.unwrap().graph_name.to_string())
+            .collect();
+        assert_eq!(
+            graphs,
+            HashSet::from([
+                "<https://dataset.trig.a>".to_string(),
+                "<https://dataset.trig.b>".to_string()
+            ])
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_compressed_graph() {
+        let _database = DATABASE.read().await;
+
+        let uuid = "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d009a";
+        let app = test_app(AppState::default()).await;
+
+        let req = post_request(uuid, post_body("https://dataset.compressed")).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        let req = test::TestRequest::get()
+            .insert_header((header::ACCEPT, "text/turtle"))
+            .insert_header((header::ACCEPT_ENCODING, "gzip"))
+            .uri(&format!("/api/assessments/{uuid}"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+        assert_eq!(
+            resp.headers().get(header::CONTENT_TYPE).unwrap(),
+            "text/turtle"
+        );
+        assert_eq!(
+            resp.headers().get(header::CONTENT_ENCODING).unwrap(),
+            "gzip"
+        );
+        let bytes = test::read_body(resp).await;
+        // The gzip magic number, and far fewer bytes than the graph itself.
+        assert_eq!(bytes[..2], [0x1f, 0x8b]);
+        assert!(bytes.len() < include_str!("../tests/assessment.ttl").len() / 2);
+    }
+
+    #[actix_web::test]
+    async fn test_assessments_turtle() {
+        let _database = DATABASE.read().await;
+
+        let app = test_app(AppState::default()).await;
+
+        let datasets = ["https://dataset.turtle.a", "https://dataset.turtle.b"];
+        for (uuid, dataset) in [
+            ("5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0038", datasets[0]),
+            ("5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0039", datasets[1]),
+        ] {
+            let mut body = post_body(dataset);
+            body["turtle_assessment"] = Value::from(format!(
+                "{}\n<{dataset}> <http://purl.org/dc/terms/title> \"{dataset}\" .\n",
+                body["turtle_assessment"].as_str().unwrap()
+            ));
+            let req = post_request(uuid, body).to_request();
+            assert!(test::call_service(&app, req).await.status().is_success());
+        }
+
+        let req = test::TestRequest::post()
+            .insert_header((header::ACCEPT, "text/turtle"))
+            .set_json(serde_json::json!({ "datasets": datasets }))
+            .uri("/api/assessments")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(
+            resp.headers().get(header::CONTENT_TYPE).unwrap(),
+            "text/turtle"
+        );
+        let bytes = test::read_body(resp).await;
+
+        let subjects: HashSet<String> = RdfParser::from_format(RdfFormat::Turtle)
+            .for_slice(&bytes)
+            .map(|quad| quad.unwrap().subject.to_string())
+            .collect();
+        for dataset in datasets {
+            assert!(subjects.contains(&format!("<{dataset}>")));
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_assessments_jsonld() {
+        let _database = DATABASE.read().await;
+
+        let app = test_app(AppState::default()).await;
+
+        let dataset = "https://dataset.jsonld";
+        let req =
+            post_request("5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0040", post_body(dataset)).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        for (datasets, quads) in [(vec![dataset], 1..usize::MAX), (vec![], 0..1)] {
+            let req = test::TestRequest::post()
+                .insert_header((header::ACCEPT, "application/ld+json"))
+                .set_json(serde_json::json!({ "datasets": datasets }))
+                .uri("/api/assessments")
+                .to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(
+                resp.headers().get(header::CONTENT_TYPE).unwrap(),
+                "application/ld+json"
+            );
+            let bytes = test::read_body(resp).await;
+
+            let parsed = RdfParser::from_format(graph::JSON_LD)
+                .for_slice(&bytes)
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            assert!(quads.contains(&parsed.len()), "{}", parsed.len());
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_percentile_rank() {
+        let _database = DATABASE.read().await;
+
+        let accessibility = "https://data.norge.no/vocabulary/dcatno-mqa#accessibility";
+        let app = test_app(AppState::default()).await;
+
+        let peers = [
+            (
+                "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0041",
+                "https://dataset.rank.a",
+                20,
+            ),
+            (
+                "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0042",
+                "https://dataset.rank.b",
+                50,
+            ),
+            (
+                "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0043",
+                "https://dataset.rank.c",
+                70,
+            ),
+            (
+                "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0044",
+                "https://dataset.rank.d",
+                90,
+            ),
+        ];
+        for (uuid, dataset, score) in peers {
+            let mut body = post_body(dataset);
+            for dimension in body["scores"]["dataset"]["dimensions"]
+                .as_array_mut()
+                .unwrap()
+            {
+                if dimension["id"] == accessibility {
+                    dimension["score"] = Value::from(score);
+                }
+            }
+            let req = post_request(uuid, body).to_request();
+            assert!(test::call_service(&app, req).await.status().is_success());
+        }
+
+        let rank = |dataset_uri: &str, peer_uris: &[&str]| {
+            test::TestRequest::post()
+                .set_json(serde_json::json!({
+                    "dataset_uri": dataset_uri,
+                    "peer_uris": peer_uris,
+                    "dimension": accessibility,
+                }))
+                .uri("/api/scores/percentile-rank")
+                .to_request()
+        };
+
+        let peer_uris: Vec<&str> = peers.iter().map(|(_, dataset, _)| *dataset).collect();
+        let body: Value = test::call_and_read_body_json(&app, rank(peer_uris[2], &peer_uris)).await;
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "dataset_uri": "https://dataset.rank.c",
+                "dimension": accessibility,
+                "ratio": 0.7,
+                "percentile_rank": 62.5,
+                "ranked_count": 4,
+            })
+        );
+
+        // The dataset is ranked among its peers even when it is not one of them.
+        let body: Value =
+            test::call_and_read_body_json(&app, rank(peer_uris[3], &peer_uris[..3])).await;
+        assert_eq!(body["percentile_rank"], Value::from(87.5));
+        assert_eq!(body["ranked_count"], Value::from(4));
+
+        let resp = test::call_service(&app, rank("https://dataset.rank.none", &peer_uris)).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn test_stream_aggregations() {
+        let _database = DATABASE.read().await;
+
+        let accessibility = "https://data.norge.no/vocabulary/dcatno-mqa#accessibility";
+        let app = test_app(AppState::default()).await;
+
+        let mut datasets = Vec::new();
+        for (i, score) in [10, 35, 70, 85, 100].into_iter().enumerate() {
+            let dataset = format!("https://dataset.stream.{i}");
+            let mut body = post_body(&dataset);
+            for dimension in body["scores"]["dataset"]["dimensions"]
+                .as_array_mut()
+                .unwrap()
+            {
+                if dimension["id"] == accessibility {
+                    dimension["score"] = Value::from(score);
+                }
+            }
+            let uuid = format!("5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d00{}", 49 + i);
+            let req = post_request(&uuid, body).to_request();
+            assert!(test::call_service(&app, req).await.status().is_success());
+            datasets.push(dataset);
+        }
+        // Duplicates are aggregated once, unknown datasets are only counted.
+        datasets.push(datasets[0].clone());
+        datasets.push("https://dataset.stream.unknown".to_string());
+
+        let req = test::TestRequest::post()
+            .insert_header((header::ACCEPT_ENCODING, "gzip"))
+            .set_json(serde_json::json!({ "datasets": datasets }))
+            .uri("/api/aggregations/stream?batch_size=2")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(
+            resp.headers().get(header::CONTENT_TYPE).unwrap(),
+            "text/event-stream"
+        );
+        assert_eq!(
+            resp.headers().get(header::CONTENT_ENCODING).unwrap(),
+            "identity"
+        );
+        let body = String::from_utf8(test::read_body(resp).await.to_vec()).unwrap();
+        let events: Vec<(&str, Value)> = body
+            .split_terminator("\n\n")
+            .map(|event| {
+                let (name, data) = event.split_once('\n').unwrap();
+                (
+                    name.strip_prefix("event: ").unwrap(),
+                    serde_json::from_str(data.strip_prefix("data: ").unwrap()).unwrap(),
+                )
+            })
+            .collect();
+
+        let progress: Vec<(i64, i64)> = events
+            .iter()
+            .filter(|(name, _)| *name == "progress")
+            .map(|(_, data)| {
+                (
+                    data["processed"].as_i64().unwrap(),
+                    data["total"].as_i64().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(progress, vec![(2, 6), (4, 6), (6, 6)]);
+
+        let (name, streamed) = events.last().unwrap();
+        assert_eq!(*name, "aggregates");
+        let req = test::TestRequest::post()
+            .set_json(serde_json::json!({ "datasets": datasets }))
+            .uri("/api/scores")
+            .to_request();
+        let scored: Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(*streamed, scored["aggregations"]);
+        assert_eq!(streamed.as_array().unwrap().len(), 5);
+    }
+
+    #[actix_web::test]
+    async fn test_scores_median_aggregation() {
+        let _database = DATABASE.read().await;
+
+        let accessibility = "https://data.norge.no/vocabulary/dcatno-mqa#accessibility";
+        let app = test_app(AppState::default()).await;
+
+        // One very poor dataset drags the mean far below the typical score.
+        let mut datasets = Vec::new();
+        for (i, score) in [0, 90, 95, 100, 100].into_iter().enumerate() {
+            let dataset = format!("https://dataset.median.{i}");
+            let mut body = post_body(&dataset);
+            for dimension in body["scores"]["dataset"]["dimensions"]
+                .as_array_mut()
+                .unwrap()
+            {
+                if dimension["id"] == accessibility {
+                    dimension["score"] = Value::from(score);
+                }
+            }
+            let uuid = format!("5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d00{}", 55 + i);
+            let req = post_request(&uuid, body).to_request();
+            assert!(test::call_service(&app, req).await.status().is_success());
+            datasets.push(dataset);
+        }
+
+        let scores_request = |body: Value, source: &str| {
+            test::TestRequest::post()
+                .set_json(body)
+                .uri(&format!("/api/scores?source={source}"))
+                .to_request()
+        };
+        let accessibility_aggregate = |body: &Value| {
+            body["aggregations"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|aggregate| aggregate["id"] == accessibility)
+                .unwrap()
+                .clone()
+        };
+
+        for (aggregation, score) in [(None, 77.0), (Some("mean"), 77.0), (Some("median"), 95.0)] {
+            let mut body = serde_json::json!({ "datasets": datasets });
+            if let Some(aggregation) = aggregation {
+                body["aggregation"] = Value::from(aggregation);
+            }
+            let body: Value =
+                test::call_and_read_body_json(&app, scores_request(body, "table")).await;
+            let aggregate = accessibility_aggregate(&body);
+            assert_eq!(aggregate["score"], Value::from(score));
+            assert_eq!(aggregate["max_score"], Value::from(100.0));
+        }
+
+        let median = serde_json::json!({ "datasets": datasets, "aggregation": "median" });
+        let resp = test::call_service(&app, scores_request(median, "graph")).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let mode = serde_json::json!({ "datasets": datasets, "aggregation": "mode" });
+        let resp = test::call_service(&app, scores_request(mode, "table")).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn test_scores_dimension_spread() {
+        let _database = DATABASE.read().await;
+
+        let accessibility = "https://data.norge.no/vocabulary/dcatno-mqa#accessibility";
+        let app = test_app(AppState::default()).await;
+
+        // The lowest score is not the lowest ratio, since max scores differ.
+        let mut datasets = Vec::new();
+        for (i, (score, max_score)) in [(40, 50), (60, 100), (90, 100)].into_iter().enumerate() {
+            let dataset = format!("https://dataset.spread.{i}");
+            let mut body = post_body(&dataset);
+            for dimension in body["scores"]["dataset"]["dimensions"]
+                .as_array_mut()
+                .unwrap()
+            {
+                if dimension["id"] == accessibility {
+                    dimension["score"] = Value::from(score);
+                    dimension["max_score"] = Value::from(max_score);
+                }
+            }
+            let uuid = format!("5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d00{}", 60 + i);
+            let req = post_request(&uuid, body).to_request();
+            assert!(test::call_service(&app, req).await.status().is_success());
+            datasets.push(dataset);
+        }
+
+        let req = test::TestRequest::post()
+            .set_json(serde_json::json!({ "datasets": datasets }))
+            .uri("/api/scores")
+            .to_request();
+        let scored: models::DatasetsScores = test::call_and_read_body_json(&app, req).await;
+        let aggregate = scored
+            .aggregations
+            .into_iter()
+            .find(|aggregate| aggregate.id == accessibility)
+            .unwrap();
+        assert_eq!(aggregate.min_observed_score, Some(40.0));
+        assert_eq!(aggregate.max_observed_score, Some(90.0));
+        assert_eq!(aggregate.min_ratio, Some(0.6));
+        assert_eq!(aggregate.max_ratio, Some(0.9));
+    }
+
+    #[actix_web::test]
+    async fn test_scores_exclude() {
+        let _database = DATABASE.read().await;
+
+        let accessibility = "https://data.norge.no/vocabulary/dcatno-mqa#accessibility";
+        let app = test_app(AppState::default()).await;
+
+        let mut datasets = Vec::new();
+        for (i, score) in [20, 80].into_iter().enumerate() {
+            let dataset = format!("https://dataset.exclude.{i}");
+            let mut body = post_body(&dataset);
+            for dimension in body["scores"]["dataset"]["dimensions"]
+                .as_array_mut()
+                .unwrap()
+            {
+                if dimension["id"] == accessibility {
+                    dimension["score"] = Value::from(score);
+                }
+            }
+            let uuid = format!("5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d00{}", 72 + i);
+            let req = post_request(&uuid, body).to_request();
+            assert!(test::call_service(&app, req).await.status().is_success());
+            datasets.push(dataset);
+        }
+
+        let scores_request = |body: Value| {
+            test::TestRequest::post()
+                .set_json(body)
+                .uri("/api/scores")
+                .to_request()
+        };
+        let excluded: models::DatasetsScores = test::call_and_read_body_json(
+            &app,
+            scores_request(serde_json::json!({
+                "datasets": datasets,
+                "exclude": [datasets[1], "https://dataset.exclude.unknown"],
+            })),
+        )
+        .await;
+        let alone: models::DatasetsScores = test::call_and_read_body_json(
+            &app,
+            scores_request(serde_json::json!({ "datasets": [datasets[0]] })),
+        )
+        .await;
+
+        assert_eq!(
+            excluded.scores.keys().collect::<Vec<_>>(),
+            vec![&datasets[0]]
+        );
+        assert_eq!(excluded.aggregations, alone.aggregations);
+        let aggregate = excluded
+            .aggregations
+            .iter()
+            .find(|aggregate| aggregate.id == accessibility)
+            .unwrap();
+        assert_eq!(aggregate.score, 20.0);
+    }
+
+    #[actix_web::test]
+    async fn test_scores_dimension_filter() {
+        let _database = DATABASE.read().await;
+
+        let accessibility = "https://data.norge.no/vocabulary/dcatno-mqa#accessibility";
+        let app = test_app(AppState::default()).await;
+
+        let dataset = "https://dataset.dimension.filter";
+        let req =
+            post_request("5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0094", post_body(dataset)).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        for (dimensions, count) in [
+            (serde_json::json!([accessibility]), 1),
+            (serde_json::json!([]), 5),
+        ] {
+            let req = test::TestRequest::post()
+                .set_json(serde_json::json!({
+                    "datasets": [dataset],
+                    "dimensions": dimensions,
+                }))
+                .uri("/api/scores")
+                .to_request();
+            let scored: models::DatasetsScores = test::call_and_read_body_json(&app, req).await;
+            assert_eq!(scored.aggregations.len(), count);
+            assert_eq!(scored.scores[dataset].dataset.dimensions.len(), 5);
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_scores_dataset_dimensions() {
+        let _database = DATABASE.read().await;
+
+        let app = test_app(AppState::default()).await;
+
+        let dataset = "https://dataset.dataset.dimensions";
+        let mut body = post_body(dataset);
+        for (i, dimension) in body["scores"]["dataset"]["dimensions"]
+            .as_array_mut()
+            .unwrap()
+            .iter_mut()
+            .enumerate()
+        {
+            dimension["score"] = Value::from(i);
+        }
+        let mut expected: Vec<models::DatasetDimension> = body["scores"]["dataset"]["dimensions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|dimension| {
+                models::DatasetDimension::new(
+                    dimension["id"].as_str().unwrap().to_string(),
+                    dimension["score"].as_i64().unwrap() as i32,
+                    dimension["max_score"].as_i64().unwrap() as i32,
+                )
+            })
+            .collect();
+        expected.sort_by(|a, b| a.id.cmp(&b.id));
+        let req = post_request("5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0095", body).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        let req = test::TestRequest::post()
+            .set_json(serde_json::json!({
+                "datasets": [dataset, "https://dataset.dataset.dimensions.unknown"],
+            }))
+            .uri("/api/scores")
+            .to_request();
+        let scored: models::DatasetsScores = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(scored.dimensions.len(), 1);
+        assert_eq!(scored.dimensions[dataset], expected);
+
+        let req = test::TestRequest::post()
+            .insert_header((header::ACCEPT, "application/x-protobuf"))
+            .set_json(serde_json::json!({ "datasets": [dataset] }))
+            .uri("/api/scores")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        let decoded = proto::DatasetsScores::decode(test::read_body(resp).await).unwrap();
+        let expected: Vec<proto::DatasetDimension> = expected.into_iter().map(Into::into).collect();
+        assert_eq!(decoded.dimensions[dataset].dimensions, expected);
+    }
+
+    #[actix_web::test]
+    async fn test_scores_weighted_aggregation() {
+        let _database = DATABASE.read().await;
+
+        let accessibility = "https://data.norge.no/vocabulary/dcatno-mqa#accessibility";
+        let app = test_app(AppState::default()).await;
+
+        // A large, nearly complete dataset and a tiny one reaching nothing.
+        let mut datasets = Vec::new();
+        for (i, (score, max_score)) in [(90, 100), (0, 1)].into_iter().enumerate() {
+            let dataset = format!("https://dataset.weighted.{i}");
+            let mut body = post_body(&dataset);
+            for dimension in body["scores"]["dataset"]["dimensions"]
+                .as_array_mut()
+                .unwrap()
+            {
+                if dimension["id"] == accessibility {
+                    dimension["score"] = Value::from(score);
+                    dimension["max_score"] = Value::from(max_score);
+                }
+            }
+            let uuid = format!("5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d00{}", 70 + i);
+            let req = post_request(&uuid, body).to_request();
+            assert!(test::call_service(&app, req).await.status().is_success());
+            datasets.push(dataset);
+        }
+
+        let mut aggregates = Vec::new();
+        for aggregation in ["mean", "weighted"] {
+            let req = test::TestRequest::post()
+                .set_json(serde_json::json!({ "datasets": datasets, "aggregation": aggregation }))
+                .uri("/api/scores")
+                .to_request();
+            let scored: models::DatasetsScores = test::call_and_read_body_json(&app, req).await;
+            aggregates.push(
+                scored
+                    .aggregations
+                    .into_iter()
+                    .find(|aggregate| aggregate.id == accessibility)
+                    .unwrap(),
+            );
+        }
+        let (mean, weighted) = (&aggregates[0], &aggregates[1]);
+
+        // Both average the scores, but only the weighted ratio lets the large
+        // dataset outweigh the tiny one.
+        assert_eq!((mean.score, mean.max_score), (45.0, 50.5));
+        assert_eq!((weighted.score, weighted.max_score), (45.0, 50.5));
+        assert_eq!(mean.ratio, Some(0.45));
+        assert_eq!(weighted.ratio, Some(90.0 / 101.0));
+    }
+
+    #[actix_web::test]
+    async fn test_scores_weight_by_distributions() {
+        let _database = DATABASE.read().await;
+
+        let accessibility = "https://data.norge.no/vocabulary/dcatno-mqa#accessibility";
+        let app = test_app(AppState::default()).await;
+
+        // A dataset with three distributions and a dataset with one.
+        let mut datasets = Vec::new();
+        for (i, (score, distributions)) in [(90, 3), (0, 1)].into_iter().enumerate() {
+            let dataset = format!("https://dataset.distributions.{i}");
+            let mut body = post_body(&dataset);
+            for dimension in body["scores"]["dataset"]["dimensions"]
+                .as_array_mut()
+                .unwrap()
+            {
+                if dimension["id"] == accessibility {
+                    dimension["score"] = Value::from(score);
+                }
+            }
+            let distribution = body["scores"]["distributions"][0].clone();
+            body["scores"]["distributions"] = Value::from(vec![distribution; distributions]);
+            let uuid = format!("5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d00{}", 78 + i);
+            let req = post_request(&uuid, body).to_request();
+            assert!(test::call_service(&app, req).await.status().is_success());
+            datasets.push(dataset);
+        }
+
+        let mut aggregates = Vec::new();
+        for weight_by in ["datasets", "distributions"] {
+            let req = test::TestRequest::post()
+                .set_json(serde_json::json!({ "datasets": datasets }))
+                .uri(&format!("/api/scores?weight_by={weight_by}"))
+                .to_request();
+            let scored: models::DatasetsScores = test::call_and_read_body_json(&app, req).await;
+            aggregates.push(
+                scored
+                    .aggregations
+                    .into_iter()
+                    .find(|aggregate| aggregate.id == accessibility)
+                    .unwrap(),
+            );
+        }
+        let (unweighted, weighted) = (&aggregates[0], &aggregates[1]);
+
+        // The dataset with three distributions counts three times as much.
+        assert_eq!(unweighted.score, 45.0);
+        assert_eq!(weighted.score, 67.5);
+        assert_eq!(weighted.ratio, Some(0.675));
+        assert_eq!(weighted.min_observed_score, Some(0.0));
+
+        for query in ["weight_by=dataset", "weight_by=distributions&source=graph"] {
+            let req = test::TestRequest::post()
+                .set_json(serde_json::json!({ "datasets": datasets }))
+                .uri(&format!("/api/scores?{query}"))
+                .to_request();
+            let response = test::call_service(&app, req).await;
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_scores_percentiles() {
+        let _database = DATABASE.read().await;
+
+        let accessibility = "https://data.norge.no/vocabulary/dcatno-mqa#accessibility";
+        let app = test_app(AppState::default()).await;
+
+        let mut datasets = Vec::new();
+        for (i, score) in [10, 20, 30, 40, 50].into_iter().enumerate() {
+            let dataset = format!("https://dataset.percentile.{i}");
+            let mut body = post_body(&dataset);
+            for dimension in body["scores"]["dataset"]["dimensions"]
+                .as_array_mut()
+                .unwrap()
+            {
+                if dimension["id"] == accessibility {
+                    dimension["score"] = Value::from(score);
+                }
+            }
+            let uuid = format!("5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d00{}", 65 + i);
+            let req = post_request(&uuid, body).to_request();
+            assert!(test::call_service(&app, req).await.status().is_success());
+            datasets.push(dataset);
+        }
+
+        let scores_request = |body: Value| {
+            test::TestRequest::post()
+                .set_json(body)
+                .uri("/api/scores")
+                .to_request()
+        };
+
+        let body = serde_json::json!({ "datasets": datasets, "percentiles": [25, 12.5, 90] });
+        let scored: models::DatasetsScores =
+            test::call_and_read_body_json(&app, scores_request(body)).await;
+        let percentiles = scored.percentiles.unwrap();
+        assert_eq!(percentiles.len(), 5);
+        let accessibility_percentiles = &percentiles[accessibility];
+        assert_eq!(accessibility_percentiles.len(), 3);
+        assert_eq!(accessibility_percentiles["25"], 20.0);
+        assert_eq!(accessibility_percentiles["12.5"], 15.0);
+        assert!((accessibility_percentiles["90"] - 46.0).abs() < 1e-9);
+
+        let body: Value = test::call_and_read_body_json(
+            &app,
+            scores_request(serde_json::json!({ "datasets": datasets })),
+        )
+        .await;
+        assert!(body.get("percentiles").is_none());
+
+        let body = serde_json::json!({ "datasets": datasets, "percentiles": [50, 101, -1] });
+        let resp = test::call_service(&app, scores_request(body)).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let body: Value = test::read_body_json(resp).await;
+        let fields: Vec<&str> = body["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|error| error["field"].as_str().unwrap())
+            .collect();
+        assert_eq!(fields, vec!["percentiles[1]", "percentiles[2]"]);
+
+        for option in ["stats=ci", "pivot=dimension", "as_percent=true"] {
+            let req = test::TestRequest::post()
+                .set_json(serde_json::json!({ "datasets": datasets, "percentiles": [50] }))
+                .uri(&format!("/api/scores?{option}"))
+                .to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+            let body: Value = test::read_body_json(resp).await;
+            assert_eq!(body["errors"][0]["field"], "percentiles");
+        }
+
+        let req = test::TestRequest::post()
+            .insert_header((header::ACCEPT, "application/x-protobuf"))
+            .set_json(serde_json::json!({ "datasets": datasets, "percentiles": [25] }))
+            .uri("/api/scores")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        let decoded = proto::DatasetsScores::decode(test::read_body(resp).await).unwrap();
+        assert_eq!(decoded.percentiles[accessibility].scores["25"], 20.0);
+    }
+
+    #[actix_web::test]
+    async fn test_assessments_trig_skips_corrupt_graph() {
+        let _database = DATABASE.read().await;
+
+        let app = test_app(AppState::default()).await;
+
+        let req = post_request(
+            "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0028",
+            post_body("https://dataset.skip.ok"),
+        )
+        .to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        let corrupt = "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0029";
+        let body = post_body("https://dataset.skip.corrupt");
+        let jsonld = body["jsonld_assessment"].as_str().unwrap().to_string();
+        let req = post_request(corrupt, body).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+        // Posts with graphs that do not parse are refused, so the corruption is
+        // written directly, like data stored before posts were validated.
+        let mut conn = PgPool::new().unwrap().get(QueryCount::default()).unwrap();
+        assert!(conn
+            .update_graphs(
+                Uuid::parse_str(corrupt).unwrap(),
+                "<https://dataset.skip.corrupt> is not turtle",
+                &jsonld,
+                GraphStorage::Inline,
+            )
+            .unwrap());
+
+        let req = test::TestRequest::post()
+            .insert_header((header::ACCEPT, "application/trig"))
+            .set_json(serde_json::json!({
+                "datasets": ["https://dataset.skip.ok", "https://dataset.skip.corrupt"]
+            }))
+            .uri("/api/assessments")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+        assert_eq!(
+            resp.headers().get(SKIPPED_DATASETS_HEADER).unwrap(),
+            "https://dataset.skip.corrupt"
+        );
+        let bytes = test::read_body(resp).await;
+
+        let graphs: HashSet<String> = RdfParser::from_format(RdfFormat::TriG)
+            .for_slice(&bytes)
+            .map(|quad| quad.unwrap().graph_name.to_string())
+            .collect();
+        assert_eq!(
+            graphs,
+            HashSet::from(["<https://dataset.skip.ok>".to_string()])
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_publisher_aggregate() {
+        let _database = DATABASE.read().await;
+
+        let app = test_app(AppState::default()).await;
+
+        // One publisher given in the payload, the other read from the graph.
+        let mut body = post_body("https://dataset.publisher.a");
+        body["publisher_uri"] = Value::from("https://publisher.a");
+        let req = post_request("5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0031", body).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        let mut body = post_body("https://dataset.publisher.b");
+        body["scores"]["dataset"]["dimensions"][0]["score"] = Value::from(20);
+        body["turtle_assessment"] = Value::from(format!(
+            "{}\n<https://dataset.publisher.b> <http://purl.org/dc/terms/publisher> <https://publisher.b> .\n",
+            body["turtle_assessment"].as_str().unwrap()
+        ));
+        let req = post_request("5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0032", body).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        let accessibility = |body: &Value| {
+            body["aggregations"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|a| a["id"] == "https://data.norge.no/vocabulary/dcatno-mqa#accessibility")
+                .unwrap()["score"]
+                .clone()
+        };
+        for (publisher, score) in [
+            ("https%3A%2F%2Fpublisher.a", 70.0),
+            ("https%3A%2F%2Fpublisher.b", 20.0),
+        ] {
+            let req = test::TestRequest::get()
+                .uri(&format!("/api/publishers/{publisher}/aggregate"))
+                .to_request();
+            let body: Value = test::call_and_read_body_json(&app, req).await;
+            assert_eq!(body["aggregations"].as_array().unwrap().len(), 5);
+            assert_eq!(accessibility(&body), score);
+        }
+
+        let req = test::TestRequest::get()
+            .uri("/api/publishers/https%3A%2F%2Fpublisher.none/aggregate")
+            .to_request();
+        let body: Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["publisher_uri"], "https://publisher.none");
+        assert!(body["aggregations"].as_array().unwrap().is_empty());
+    }
+
+    #[actix_web::test]
+    async fn test_full_export() {
+        let _database = DATABASE.read().await;
+
+        let datasets = ["https://dataset.export.a", "https://dataset.export.b"];
+        let app = test_app(AppState::default()).await;
+
+        for (uuid, dataset) in [
+            ("5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0026", datasets[0]),
+            ("5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0027", datasets[1]),
+        ] {
+            let req = post_request(uuid, post_body(dataset)).to_request();
+            assert!(test::call_service(&app, req).await.status().is_success());
+        }
+
+        let req = test::TestRequest::post()
+            .set_json(serde_json::json!({ "datasets": datasets }))
+            .uri("/api/assessments/full-export")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        let req = test::TestRequest::post()
+            .insert_header(("X-API-KEY", "foo"))
+            .set_json(serde_json::json!({ "datasets": datasets }))
+            .uri("/api/assessments/full-export")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+        assert_eq!(
+            resp.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/x-tar"
+        );
+        let bytes = test::read_body(resp).await;
+
+        let mut files = HashMap::new();
+        for entry in tar::Archive::new(&bytes[..]).entries().unwrap() {
+            let mut entry = entry.unwrap();
+            let mut content = String::new();
+            entry.read_to_string(&mut content).unwrap();
+            files.insert(entry.path().unwrap().display().to_string(), content);
+        }
+        assert_eq!(files.len(), 4);
+        for dataset in datasets {
+            let stem = archive::uri_hash(dataset);
+            let score: Value = serde_json::from_str(&files[&format!("{stem}.json")]).unwrap();
+            assert_eq!(score["dataset"]["id"], dataset);
+            // The ratios are computed for the export, as they are not stored.
+            assert_eq!(score["dataset"]["dimensions"][0]["ratio"], 0.7);
+            assert!(score["dataset"]["ratio"].as_f64().unwrap() > 0.0);
+            assert!(!files[&format!("{stem}.ttl")].is_empty());
+        }
+
+        let mut conn = PgConnection::establish(&database::database_url().unwrap()).unwrap();
+        let stored: String = {
+            use diesel::{ExpressionMethods, QueryDsl};
+            use schema::dataset_assessments::dsl;
+            dsl::dataset_assessments
+                .filter(dsl::dataset_uri.eq(datasets[0]))
+                .select(dsl::json_score)
+                .first(&mut conn)
+                .unwrap()
+        };
+        assert!(!stored.contains("ratio"));
+    }
+
+    #[actix_web::test]
+    async fn test_compare_datasets() {
+        let _database = DATABASE.read().await;
+
+        let app = test_app(AppState::default()).await;
+        // Dimension 0 is accessibility (max 100), dimension 1 contextuality (max 20).
+        let datasets = [
+            (
+                "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0092",
+                "https://dataset.compare-datasets.a",
+                80,
+                5,
+            ),
+            (
+                "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0093",
+                "https://dataset.compare-datasets.b",
+                50,
+                20,
+            ),
+        ];
+        for (uuid, dataset, accessibility, contextuality) in datasets {
+            let mut body = post_body(dataset);
+            body["scores"]["dataset"]["dimensions"][0]["score"] = Value::from(accessibility);
+            body["scores"]["dataset"]["dimensions"][1]["score"] = Value::from(contextuality);
+            let req = post_request(uuid, body).to_request();
+            assert!(test::call_service(&app, req).await.status().is_success());
+        }
+        let compare = |a: &str, b: &str| {
+            test::TestRequest::post()
+                .set_json(serde_json::json!({ "a": a, "b": b }))
+                .uri("/api/scores/compare")
+                .to_request()
+        };
+
+        let comparison: models::DatasetComparison = test::call_and_read_body_json(
+            &app,
+            compare(
+                "https://dataset.compare-datasets.a",
+                "https://dataset.compare-datasets.b",
+            ),
+        )
+        .await;
+        assert_eq!(comparison.dimensions.len(), 5);
+        let dimension = |id: &str| {
+            comparison
+                .dimensions
+                .iter()
+                .find(|dimension| {
+                    dimension.id == format!("https://data.norge.no/vocabulary/dcatno-mqa#{id}")
+                })
+                .unwrap()
+        };
+        let accessibility = dimension("accessibility");
+        assert_eq!(
+            (accessibility.a_score, accessibility.b_score),
+            (Some(80), Some(50))
+        );
+        assert_eq!(accessibility.a_max_score, Some(100));
+        assert!(accessibility.delta.unwrap() < 0);
+        let contextuality = dimension("contextuality");
+        assert_eq!(contextuality.b_max_score, Some(20));
+        assert!(contextuality.delta.unwrap() > 0);
+        assert_eq!(dimension("findability").delta, Some(0));
+
+        let resp = test::call_service(
+            &app,
+            compare(
+                "https://dataset.compare-datasets.a",
+                "https://dataset.compare.missing",
+            ),
+        )
+        .await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        let body: Value = test::read_body_json(resp).await;
+        assert!(body["message"]
+            .as_str()
+            .unwrap()
+            .contains("https://dataset.compare.missing"));
+
+        let resp = test::call_service(
+            &app,
+            compare("https://dataset.compare-datasets.a", "not a uri"),
+        )
+        .await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn test_scores_vs_average() {
+        // The store-wide averages must not change between the two requests.
+        let _database = DATABASE.write().await;
+
+        let accessibility = "https://data.norge.no/vocabulary/dcatno-mqa#accessibility";
+        let dataset_uri = "https://dataset.vs.average";
+        let app = test_app(AppState::default()).await;
+        let req = post_request(
+            "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0085",
+            post_body(dataset_uri),
+        )
+        .to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        let req = test::TestRequest::get()
+            .uri("/api/dimensions/overview")
+            .to_request();
+        let overview: Vec<models::DimensionOverview> =
+            test::call_and_read_body_json(&app, req).await;
+        let req = test::TestRequest::get()
+            .uri("/api/scores/vs-average?dataset=https%3A%2F%2Fdataset.vs.average")
+            .to_request();
+        let comparison: models::DatasetVsAverage = test::call_and_read_body_json(&app, req).await;
+
+        assert_eq!(comparison.dataset, dataset_uri);
+        assert_eq!(comparison.dimensions.len(), 5);
+        for dimension in &comparison.dimensions {
+            let average = overview.iter().find(|o| o.id == dimension.id).unwrap();
+            assert!((dimension.average_ratio - average.average_ratio).abs() < 1e-12);
+            // serde_json may round the parsed floats by an ulp.
+            assert!((dimension.delta - (dimension.ratio - dimension.average_ratio)).abs() < 1e-12);
+        }
+        let dimension = comparison
+            .dimensions
+            .iter()
+            .find(|dimension| dimension.id == accessibility)
+            .unwrap();
+        assert_eq!(dimension.ratio, 0.7);
+
+        let req = test::TestRequest::get()
+            .uri("/api/scores/vs-average?dataset=https%3A%2F%2Fdataset.unknown")
+            .to_request();
+        let comparison: models::DatasetVsAverage = test::call_and_read_body_json(&app, req).await;
+        assert!(comparison.dimensions.is_empty());
+
+        let req = test::TestRequest::get()
+            .uri("/api/scores/vs-average")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn test_list_assessments() {
+        // Nothing may be stored while the pages are compared with the store.
+        let _database = DATABASE.write().await;
+
+        let app = test_app(AppState::default()).await;
+        let req = post_request(
+            "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0084",
+            post_body("https://dataset.listed"),
+        )
+        .to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        let mut conn = PgConnection::establish(&database::database_url().unwrap()).unwrap();
+        let stored: i64 = {
+            use diesel::QueryDsl;
+            use schema::dataset_assessments::dsl;
+            dsl::dataset_assessments
+                .count()
+                .get_result(&mut conn)
+                .unwrap()
+        };
+        let page = |query: &str| {
+            test::TestRequest::get()
+                .uri(&format!("/api/assessments{query}"))
+                .to_request()
+        };
+
+        // The first page holds up to the default page size, ordered by uri.
+        let first: Vec<models::AssessmentSummary> =
+            test::call_and_read_body_json(&app, page("")).await;
+        assert_eq!(first.len() as i64, stored.min(100));
+        assert!(first
+            .windows(2)
+            .all(|pair| pair[0].dataset_uri <= pair[1].dataset_uri));
+
+        let second: Vec<models::AssessmentSummary> =
+            test::call_and_read_body_json(&app, page("?limit=2&offset=1")).await;
+        assert_eq!(second, first[1..3]);
+
+        let past_end: Vec<models::AssessmentSummary> =
+            test::call_and_read_body_json(&app, page(&format!("?offset={stored}"))).await;
+        assert!(past_end.is_empty());
+
+        let resp = test::call_service(&app, page("?limit=0")).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn test_list_assessments_links() {
+        // Nothing may be stored while the total is compared with the store.
+        let _database = DATABASE.write().await;
+
+        let app = test_app(AppState::default()).await;
+        // At least three assessments, so the second page of one is in the middle.
+        for (uuid, dataset_uri) in [
+            (
+                "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0087",
+                "https://dataset.linked.a",
+            ),
+            (
+                "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0088",
+                "https://dataset.linked.b",
+            ),
+            (
+                "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0089",
+                "https://dataset.linked.c",
+            ),
+        ] {
+            let req = post_request(uuid, post_body(dataset_uri)).to_request();
+            assert!(test::call_service(&app, req).await.status().is_success());
+        }
+
+        let mut conn = PgConnection::establish(&database::database_url().unwrap()).unwrap();
+        let stored: i64 = {
+            use diesel::QueryDsl;
+            use schema::dataset_assessments::dsl;
+            dsl::dataset_assessments
+                .count()
+                .get_result(&mut conn)
+                .unwrap()
+        };
+        let page = |query: &str| {
+            test::TestRequest::get()
+                .uri(&format!("/api/assessments{query}"))
+                .to_request()
+        };
+
+        let resp = test::call_service(&app, page("?limit=1&offset=1")).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers().get(TOTAL_COUNT_HEADER).unwrap(),
+            &stored.to_string()
+        );
+        assert_eq!(
+            resp.headers().get(header::LINK).unwrap(),
+            "</api/assessments?limit=1&offset=2>; rel=\"next\", \
+             </api/assessments?limit=1&offset=0>; rel=\"prev\""
+        );
+
+        // The first page has no previous page, and the last no next.
+        let resp = test::call_service(&app, page("?limit=2")).await;
+        assert_eq!(
+            resp.headers().get(header::LINK).unwrap(),
+            "</api/assessments?limit=2&offset=2>; rel=\"next\""
+        );
+        let resp = test::call_service(&app, page(&format!("?limit=2&offset={}", stored - 1))).await;
+        assert_eq!(
+            resp.headers().get(header::LINK).unwrap(),
+            &format!(
+                "</api/assessments?limit=2&offset={}>; rel=\"prev\"",
+                stored - 3
+            )
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_annotations() {
+        let _database = DATABASE.read().await;
+
+        let uuid = "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0083";
+        let dataset_uri = "https://dataset.annotated";
+        let app = test_app(AppState::default()).await;
+        let req = post_request(uuid, post_body(dataset_uri)).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        let annotation = serde_json::json!({ "key": "review", "value": "manually reviewed" });
+        let set = |uuid: &str| {
+            test::TestRequest::post()
+                .set_json(&annotation)
+                .uri(&format!("/api/assessments/{uuid}/annotations"))
+        };
+        let resp = test::call_service(&app, set(uuid).to_request()).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        let req = set(uuid).insert_header(("X-API-KEY", "foo")).to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let req = set("5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1dffff")
+            .insert_header(("X-API-KEY", "foo"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        // Posting the assessment again, with other content, keeps the annotation.
+        let mut body = post_body(dataset_uri);
+        body["catalog_uri"] = Value::from(format!("https://catalog.{}", Uuid::new_v4()));
+        let req = post_request(uuid, body).to_request();
+        assert_eq!(
+            test::call_service(&app, req).await.status(),
+            StatusCode::ACCEPTED
+        );
+        let get = || {
+            test::TestRequest::get()
+                .uri(&format!("/api/assessments/{uuid}/annotations"))
+                .to_request()
+        };
+        let stored: Vec<models::Annotation> = test::call_and_read_body_json(&app, get()).await;
+        assert_eq!(
+            stored,
+            vec![models::Annotation::new(
+                "review".to_string(),
+                "manually reviewed".to_string()
+            )]
+        );
+
+        let req = test::TestRequest::delete()
+            .insert_header(("X-API-KEY", "foo"))
+            .uri(&format!("/api/assessments/{uuid}/annotations/review"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+        let stored: Vec<models::Annotation> = test::call_and_read_body_json(&app, get()).await;
+        assert!(stored.is_empty());
+    }
+
+    #[actix_web::test]
+    async fn test_require_api_key_for_reads() {
+        let _database = DATABASE.read().await;
+
+        let uuid = "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0082";
+        let dataset_uri = "https://dataset.read.access";
+        let app = test_app(AppState {
+            read_access: ReadAccess {
+                require_api_key: true,
+            },
+            ..Default::default()
+        })
+        .await;
+        let req = post_request(uuid, post_body(dataset_uri)).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        let datasets = serde_json::json!({ "datasets": [dataset_uri] });
+        let reads = || {
+            [
+                test::TestRequest::get().uri(&format!("/api/assessments/{uuid}")),
+                test::TestRequest::post()
+                    .set_json(&datasets)
+                    .uri("/api/scores"),
+                test::TestRequest::post()
+                    .set_json(&datasets)
+                    .uri("/api/assessments"),
+                test::TestRequest::get().uri("/api/assessments/changed?since=2000-01-01T00:00:00Z"),
+                test::TestRequest::get().uri(&format!("/api/assessments/{uuid}/graph")),
+                test::TestRequest::get().uri(&format!(
+                    "/api/assessments/{uuid}/dimensions/{}/trend",
+                    "https%3A%2F%2Fdata.norge.no%2Fvocabulary%2Fdcatno-mqa%23accessibility"
+                )),
+                test::TestRequest::get().uri(&format!("/api/assessments/{uuid}/distributions")),
+                test::TestRequest::get().uri(&format!("/api/assessments/{uuid}/uri")),
+                test::TestRequest::get().uri(&format!("/api/assessments/{uuid}/annotations")),
+                test::TestRequest::get().uri("/api/dimensions/overview"),
+                test::TestRequest::get().uri("/api/catalogs/counts"),
+                test::TestRequest::get()
+                    .uri("/api/catalogs/compare?a=https://catalog.read.a&b=https://catalog.read.b"),
+                test::TestRequest::get()
+                    .uri("/api/publishers/https%3A%2F%2Fpublisher.read.access/aggregate"),
+                test::TestRequest::post()
+                    .set_json(&datasets)
+                    .uri("/api/aggregations/stream"),
+                test::TestRequest::get().uri(&format!("/api/assessments/{uuid}/score")),
+                test::TestRequest::post()
+                    .set_json(serde_json::json!({ "a": dataset_uri, "b": dataset_uri }))
+                    .uri("/api/scores/compare"),
+                test::TestRequest::get()
+                    .uri("/api/scores/vs-average?dataset=https%3A%2F%2Fdataset.read.access"),
+                test::TestRequest::post()
+                    .set_json(serde_json::json!({
+                        "dataset_uri": dataset_uri,
+                        "peer_uris": [],
+                        "dimension": "https://data.norge.no/vocabulary/dcatno-mqa#accessibility",
+                    }))
+                    .uri("/api/scores/percentile-rank"),
+            ]
+        };
+        for req in reads() {
+            let resp = test::call_service(&app, req.to_request()).await;
+            assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        }
+        for req in reads() {
+            let req = req.insert_header(("X-API-KEY", "bar")).to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        }
+        for req in reads() {
+            let req = req.insert_header(("X-API-KEY", "foo")).to_request();
+            let path = req.path().to_string();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), StatusCode::OK, "{path}");
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_stream_scores_csv() {
+        // Nothing may be stored while the store is compared with the export.
+        let _database = DATABASE.write().await;
+
+        let accessibility = "https://data.norge.no/vocabulary/dcatno-mqa#accessibility";
+        let app = test_app(AppState::default()).await;
+        let req = post_request(
+            "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0081",
+            post_body("https://dataset.csv,stream"),
+        )
+        .to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        let req = test::TestRequest::get()
+            .uri("/api/scores/stream.csv")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        let req = test::TestRequest::get()
+            .insert_header(("X-API-KEY", "foo"))
+            .uri("/api/scores/stream.csv")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+        assert_eq!(
+            resp.headers().get(header::CONTENT_TYPE).unwrap(),
+            "text/csv; charset=utf-8"
+        );
+        let body = test::read_body(resp).await;
+        let document = std::str::from_utf8(&body).unwrap();
+        let rows: Vec<&str> = document
+            .strip_suffix("\r\n")
+            .unwrap()
+            .split("\r\n")
+            .collect();
+
+        let mut conn = PgConnection::establish(&database::database_url().unwrap()).unwrap();
+        let dimension_ids: Vec<String> = {
+            use diesel::QueryDsl;
+            use schema::dimensions::dsl;
+            dsl::dimensions
+                .select(dsl::id)
+                .distinct()
+                .order(dsl::id)
+                .load(&mut conn)
+                .unwrap()
+        };
+        let datasets: i64 = {
+            use diesel::QueryDsl;
+            use schema::dataset_assessments::dsl;
+            dsl::dataset_assessments
+                .count()
+                .get_result(&mut conn)
+                .unwrap()
+        };
+        let mut header = vec!["dataset_uri".to_string()];
+        header.extend(dimension_ids);
+        assert_eq!(rows[0], header.join(","));
+        assert_eq!(rows.len() as i64 - 1, datasets);
+
+        // The uri holds a comma, so it is quoted.
+        let column = header.iter().position(|id| id == accessibility).unwrap();
+        let cells: Vec<&str> = rows
+            .iter()
+            .find_map(|row| row.strip_prefix("\"https://dataset.csv,stream\","))
+            .unwrap()
+            .split(',')
+            .collect();
+        assert_eq!(cells.len(), header.len() - 1);
+        assert_eq!(cells[column - 1], "70");
+    }
+
+    #[actix_web::test]
+    async fn test_score_histogram() {
+        let _database = DATABASE.read().await;
+
+        let app = test_app(AppState::default()).await;
+
+        let mut datasets = Vec::new();
+        for (i, score) in [0, 10, 30, 50, 75, 100].into_iter().enumerate() {
+            let dataset = format!("https://dataset.histogram.{score}");
+            let mut body = post_body(&dataset);
+            body["scores"]["dataset"]["dimensions"] = serde_json::json!([{
+                "id": "https://data.norge.no/vocabulary/dcatno-mqa#accessibility",
+                "metrics": [],
+                "score": score,
+                "max_score": 100,
+            }]);
+            let uuid = format!("5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d00{:x}", 0x9b + i);
+            let req = post_request(&uuid, body).to_request();
+            assert!(test::call_service(&app, req).await.status().is_success());
+            datasets.push(dataset);
+        }
+
+        let histogram_request = |body: Value| {
+            test::TestRequest::post()
+                .set_json(body)
+                .uri("/api/scores/histogram")
+                .to_request()
+        };
+        let histogram: models::ScoreHistogram = test::call_and_read_body_json(
+            &app,
+            histogram_request(serde_json::json!({ "datasets": datasets })),
+        )
+        .await;
+        assert_eq!(histogram.bucket_edges, [0.0, 0.25, 0.5, 0.75, 1.0]);
+        assert_eq!(histogram.counts, [2, 1, 1, 2]);
+
+        let histogram: models::ScoreHistogram = test::call_and_read_body_json(
+            &app,
+            histogram_request(serde_json::json!({
+                "datasets": datasets,
+                "exclude": [datasets[0]],
+                "buckets": 2,
+            })),
+        )
+        .await;
+        assert_eq!(histogram.bucket_edges, [0.0, 0.5, 1.0]);
+        assert_eq!(histogram.counts, [2, 3]);
+
+        let resp = test::call_service(
+            &app,
+            histogram_request(serde_json::json!({ "datasets": datasets, "buckets": 0 })),
+        )
+        .await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn test_export_scores_csv() {
+        let _database = DATABASE.read().await;
+
+        let app = test_app(AppState::default()).await;
+        let dataset = "https://dataset.csv.export";
+        let req =
+            post_request("5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0096", post_body(dataset)).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        let req = test::TestRequest::post()
+            .set_json(serde_json::json!({ "datasets": [dataset] }))
+            .uri("/api/scores/export.csv")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+        assert_eq!(
+            resp.headers().get(header::CONTENT_TYPE).unwrap(),
+            "text/csv; charset=utf-8"
+        );
+        let body = test::read_body(resp).await;
+        let rows: Vec<&str> = std::str::from_utf8(&body)
+            .unwrap()
+            .strip_suffix("\r\n")
+            .unwrap()
+            .split("\r\n")
+            .collect();
+        assert_eq!(rows.len(), 6);
+        assert_eq!(rows[0], "dimension_id,avg_score,avg_max_score,ratio");
+        assert_eq!(
+            rows[1],
+            "https://data.norge.no/vocabulary/dcatno-mqa#accessibility,70,100,0.7"
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_parse_with_timeout() {
+        let parsed = parse_with_timeout(Duration::from_secs(5), || Ok(42)).await;
+        assert_eq!(parsed.unwrap(), 42);
+
+        let parsed = parse_with_timeout(Duration::from_millis(10), || {
+            std::thread::sleep(Duration::from_millis(500));
+            Ok(())
+        })
+        .await;
+        assert!(matches!(parsed, Err(Error::GraphParseTimeout(10))));
+        let resp = actix_web::ResponseError::error_response(&parsed.unwrap_err());
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn test_graph_too_large() {
+        let app = test_app(AppState::default()).await;
+
+        let mut body = post_body("https://dataset.too.large");
+        body["turtle_assessment"] = Value::from("#".repeat(*MAX_GRAPH_BYTES + 1));
+        let req = post_request("5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0007", body).to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        let body: Value = test::read_body_json(resp).await;
+        assert!(body["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("turtle_assessment exceeds"));
+    }
+
+    #[actix_web::test]
+    async fn test_dimensions_overview() {
+        let _database = DATABASE.read().await;
+
+        let app = test_app(AppState::default()).await;
+
+        for (uuid, dataset, score) in [
+            (
+                "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0008",
+                "https://dataset.overview.a",
+                20,
+            ),
+            (
+                "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0009",
+                "https://dataset.overview.b",
+                60,
+            ),
+        ] {
+            let mut body = post_body(dataset);
+            body["scores"]["dataset"]["dimensions"] = serde_json::json!([
+                { "id": "https://dimension.overview#a", "metrics": [], "score": score, "max_score": 80 },
+                { "id": "https://dimension.overview#b", "metrics": [], "score": 0, "max_score": 40 },
+            ]);
+            let req = post_request(uuid, body).to_request();
+            assert!(test::call_service(&app, req).await.status().is_success());
+        }
+
+        let req = test::TestRequest::get()
+            .uri("/api/dimensions/overview")
+            .to_request();
+        let body: Vec<Value> = test::call_and_read_body_json(&app, req).await;
+        let dimension = |id: &str| body.iter().find(|d| d["id"] == id).unwrap().clone();
+
+        assert_eq!(
+            dimension("https://dimension.overview#a"),
+            serde_json::json!({
+                "id": "https://dimension.overview#a",
+                "average_ratio": 0.5,
+                "dataset_count": 2
+            })
+        );
+        assert_eq!(
+            dimension("https://dimension.overview#b")["average_ratio"],
+            0.0
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_write_freeze() {
+        let _database = DATABASE.read().await;
+
+        let uuid = "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d000a";
+        let write_freeze = WriteFreeze::new(Some(Utc::now() + chrono::TimeDelta::seconds(120)));
+        let app = test_app(AppState {
+            write_freeze,
+            ..Default::default()
+        })
+        .await;
+
+        let req = post_request(uuid, post_body("https://dataset.freeze")).to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let retry_after: u64 = resp
+            .headers()
+            .get(header::RETRY_AFTER)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!((1..=120).contains(&retry_after));
+
+        let req = test::TestRequest::post()
+            .insert_header(("X-API-KEY", "foo"))
+            .uri("/api/admin/prune-orphans")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert!(resp.headers().contains_key(header::RETRY_AFTER));
+
+        let req = test::TestRequest::post()
+            .set_json(serde_json::json!({ "datasets": ["https://dataset.freeze"] }))
+            .uri("/api/scores")
+            .to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        let req = test::TestRequest::post()
+            .insert_header(("X-API-KEY", "foo"))
+            .set_json(serde_json::json!({}))
+            .uri("/api/admin/write-freeze")
+            .to_request();
+        let body: Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body, serde_json::json!({}));
+
+        let req = post_request(uuid, post_body("https://dataset.freeze")).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+    }
+
+    #[actix_web::test]
+    async fn test_scores_pivot() {
+        let _database = DATABASE.read().await;
+
+        let datasets = ["https://dataset.pivot.a", "https://dataset.pivot.b"];
+        let app = test_app(AppState::default()).await;
+
+        for (uuid, dataset) in [
+            ("5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d000b", datasets[0]),
+            ("5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d000c", datasets[1]),
+        ] {
+            let req = post_request(uuid, post_body(dataset)).to_request();
+            assert!(test::call_service(&app, req).await.status().is_success());
+        }
+
+        let req = test::TestRequest::post()
+            .set_json(serde_json::json!({ "datasets": datasets }))
+            .uri("/api/scores")
+            .to_request();
+        let by_dataset: Value = test::call_and_read_body_json(&app, req).await;
+
+        let req = test::TestRequest::post()
+            .set_json(serde_json::json!({ "datasets": datasets }))
+            .uri("/api/scores?pivot=dimension")
+            .to_request();
+        let pivoted: Value = test::call_and_read_body_json(&app, req).await;
+
+        let dimensions = pivoted["dimensions"].as_object().unwrap();
+        assert_eq!(dimensions.len(), 5);
+        for (id, entries) in dimensions {
+            let entries = entries.as_array().unwrap();
+            assert_eq!(entries.len(), datasets.len());
+            for (entry, dataset) in entries.iter().zip(datasets) {
+                assert_eq!(entry["dataset_uri"], dataset);
+                let dimension = by_dataset["scores"][dataset]["dataset"]["dimensions"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .find(|d| d["id"] == id.as_str())
+                    .unwrap();
+                assert_eq!(entry["score"], dimension["score"]);
+                assert_eq!(entry["max_score"], dimension["max_score"]);
+            }
+        }
+
+        // Like the aggregations, the pivot is limited to the requested dimensions.
+        let accessibility = "https://data.norge.no/vocabulary/dcatno-mqa#accessibility";
+        let req = test::TestRequest::post()
+            .set_json(serde_json::json!({ "datasets": datasets, "dimensions": [accessibility] }))
+            .uri("/api/scores?pivot=dimension")
+            .to_request();
+        let pivoted: Value = test::call_and_read_body_json(&app, req).await;
+        let dimensions = pivoted["dimensions"].as_object().unwrap();
+        assert_eq!(dimensions.len(), 1);
+        assert_eq!(
+            dimensions[accessibility].as_array().unwrap().len(),
+            datasets.len()
+        );
+
+        let req = test::TestRequest::post()
+            .set_json(serde_json::json!({ "datasets": datasets }))
+            .uri("/api/scores?pivot=metric")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn test_scores_quoted_uri() {
+        let _database = DATABASE.read().await;
+
+        let dataset = "https://dataset.quote/o'brien";
+        let app = test_app(AppState::default()).await;
+
+        let mut body = post_body(dataset);
+        body["scores"]["dataset"]["dimensions"][0]["score"] = Value::from(33);
+        let req = post_request("5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0036", body).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        // Inlined in the query, the second uri would match every stored dimension.
+        let req = test::TestRequest::post()
+            .set_json(serde_json::json!({
+                "datasets": [dataset, "https://dataset.quote/x')OR('a'='a"]
+            }))
+            .uri("/api/scores")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+        let body: Value = test::read_body_json(resp).await;
+        assert_eq!(body["scores"].as_object().unwrap().len(), 1);
+        let aggregations = body["aggregations"].as_array().unwrap();
+        assert_eq!(aggregations.len(), 5);
+        assert_eq!(
+            aggregations[0]["id"],
+            "https://data.norge.no/vocabulary/dcatno-mqa#accessibility"
+        );
+        assert_eq!(aggregations[0]["score"], 33.0);
+    }
+
+    #[actix_web::test]
+    async fn test_scores_large_uri_list() {
+        let _database = DATABASE.read().await;
+
+        let datasets = ["https://dataset.large.a", "https://dataset.large.b"];
+        let app = test_app(AppState::default()).await;
+
+        for (uuid, dataset) in [
+            ("5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d000d", datasets[0]),
+            ("5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d000e", datasets[1]),
+        ] {
+            let req = post_request(uuid, post_body(dataset)).to_request();
+            assert!(test::call_service(&app, req).await.status().is_success());
+        }
+
+        let small: Vec<String> = datasets.iter().map(|d| d.to_string()).collect();
+        let large: Vec<String> = small
+            .iter()
+            .cloned()
+            .chain((0..1000).map(|i| format!("https://dataset.large.missing/{i}")))
+            .collect();
+
+        let mut conn = PgPool::new().unwrap().get(QueryCount::default()).unwrap();
+        assert_eq!(
+            conn.dimension_aggregates(&large, &[], Aggregation::Mean, WeightBy::Datasets)
+                .unwrap(),
+            conn.dimension_aggregates(&small, &[], Aggregation::Mean, WeightBy::Datasets)
+                .unwrap()
+        );
+        assert_eq!(
+            conn.dimension_aggregates(&large, &[], Aggregation::Mean, WeightBy::Datasets)
+                .unwrap()
+                .len(),
+            5
+        );
+        assert_eq!(
+            conn.json_scores(&large).unwrap(),
+            conn.json_scores(&small).unwrap()
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_scores_dimension_thresholds() {
+        let _database = DATABASE.read().await;
+
+        let app = test_app(AppState::default()).await;
+
+        // Dimension 0 is accessibility (max 100), dimension 1 contextuality (max 20).
+        let datasets = [
+            (
+                "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0033",
+                "https://dataset.threshold.both",
+                80,
+                20,
+            ),
+            (
+                "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0034",
+                "https://dataset.threshold.low.context",
+                90,
+                5,
+            ),
+            (
+                "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0035",
+                "https://dataset.threshold.low.access",
+                50,
+                20,
+            ),
+        ];
+        for (uuid, dataset, accessibility, contextuality) in datasets {
+            let mut body = post_body(dataset);
+            body["scores"]["dataset"]["dimensions"][0]["score"] = Value::from(accessibility);
+            body["scores"]["dataset"]["dimensions"][1]["score"] = Value::from(contextuality);
+            let req = post_request(uuid, body).to_request();
+            assert!(test::call_service(&app, req).await.status().is_success());
+        }
+
+        let req = test::TestRequest::post()
+            .set_json(serde_json::json!({
+                "datasets": datasets.map(|(_, dataset, _, _)| dataset),
+                "dimension_thresholds": {
+                    "https://data.norge.no/vocabulary/dcatno-mqa#accessibility": 0.8,
+                    "https://data.norge.no/vocabulary/dcatno-mqa#contextuality": 0.5
+                }
+            }))
+            .uri("/api/scores")
+            .to_request();
+        let body: Value = test::call_and_read_body_json(&app, req).await;
+        let returned: Vec<&String> = body["scores"].as_object().unwrap().keys().collect();
+        assert_eq!(returned, vec!["https://dataset.threshold.both"]);
+        assert_eq!(body["aggregations"][0]["score"], 80.0);
+
+        let req = test::TestRequest::post()
+            .set_json(serde_json::json!({
+                "datasets": ["https://dataset.threshold.both"],
+                "dimension_thresholds": {
+                    "https://data.norge.no/vocabulary/dcatno-mqa#accessibility": 1.5
+                }
+            }))
+            .uri("/api/scores")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let body: Value = test::read_body_json(resp).await;
+        assert_eq!(
+            body["errors"][0]["field"],
+            "dimension_thresholds.https://data.norge.no/vocabulary/dcatno-mqa#accessibility"
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_aggregations_sorted_by_id() {
+        let _database = DATABASE.read().await;
+
+        let app = test_app(AppState::default()).await;
+
+        let req = post_request(
+            "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0025",
+            post_body("https://dataset.sorted"),
+        )
+        .to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        let req = test::TestRequest::post()
+            .insert_header(ContentType::json())
+            .set_json(serde_json::json!({ "datasets": ["https://dataset.sorted"] }))
+            .uri("/api/scores")
+            .to_request();
+        let body: Value = test::call_and_read_body_json(&app, req).await;
+        let ids: Vec<&str> = body["aggregations"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|aggregate| aggregate["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(ids.len(), 5);
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[actix_web::test]
+    async fn test_catalog_counts() {
+        let _database = DATABASE.read().await;
+
+        let app = test_app(AppState::default()).await;
+
+        for (uuid, dataset, catalog, score) in [
+            (
+                "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d000f",
+                "https://dataset.counts.a",
+                "https://catalog.counts.a",
+                100,
+            ),
+            (
+                "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0010",
+                "https://dataset.counts.b",
+                "https://catalog.counts.a",
+                300,
+            ),
+            (
+                "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0011",
+                "https://dataset.counts.c",
+                "https://catalog.counts.b",
+                0,
+            ),
+        ] {
+            let mut body = post_body(dataset);
+            body["catalog_uri"] = Value::from(catalog);
+            body["scores"]["dataset"]["score"] = Value::from(score);
+            body["scores"]["dataset"]["max_score"] = Value::from(400);
+            let req = post_request(uuid, body).to_request();
+            assert!(test::call_service(&app, req).await.status().is_success());
+        }
+
+        let req = test::TestRequest::get()
+            .uri("/api/catalogs/counts")
+            .to_request();
+        let body: Vec<Value> = test::call_and_read_body_json(&app, req).await;
+        let position = |catalog: &str| body.iter().position(|c| c["catalog_uri"] == catalog);
+
+        let a = position("https://catalog.counts.a").unwrap();
+        let b = position("https://catalog.counts.b").unwrap();
+        assert!(a < b);
+        assert_eq!(body[a]["assessment_count"], 2);
+        assert_eq!(body[a]["avg_overall_ratio"], 0.5);
+        assert_eq!(body[b]["assessment_count"], 1);
+        assert_eq!(body[b]["avg_overall_ratio"], 0.0);
+    }
+
+    #[actix_web::test]
+    async fn test_compare_catalogs() {
+        let _database = DATABASE.read().await;
+
+        let app = test_app(AppState::default()).await;
+
+        for (uuid, dataset, catalog, score) in [
+            (
+                "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0019",
+                "https://dataset.compare.a",
+                "https://catalog.compare.a",
+                20,
+            ),
+            (
+                "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d001a",
+                "https://dataset.compare.b",
+                "https://catalog.compare.b",
+                80,
+            ),
+        ] {
+            let mut body = post_body(dataset);
+            body["catalog_uri"] = Value::from(catalog);
+            for dimension in body["scores"]["dataset"]["dimensions"]
+                .as_array_mut()
+                .unwrap()
+            {
+                dimension["score"] = Value::from(score);
+                dimension["max_score"] = Value::from(100);
+            }
+            let req = post_request(uuid, body).to_request();
+            assert!(test::call_service(&app, req).await.status().is_success());
+        }
+
+        let req = test::TestRequest::get()
+            .uri("/api/catalogs/compare?a=https://catalog.compare.a&b=https://catalog.compare.b")
+            .to_request();
+        let comparison: models::CatalogComparison = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(comparison.a, "https://catalog.compare.a");
+        assert_eq!(comparison.dimensions.len(), 5);
+        for dimension in comparison.dimensions {
+            assert_eq!(dimension.a, Some(0.2));
+            assert_eq!(dimension.b, Some(0.8));
+            assert!((dimension.delta.unwrap() - 0.6).abs() < 1e-9);
+        }
+
+        let req = test::TestRequest::get()
+            .uri("/api/catalogs/compare?a=not%20a%20uri")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let body: Value = test::read_body_json(resp).await;
+        let fields: Vec<&str> = body["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| e["field"].as_str().unwrap())
+            .collect();
+        assert_eq!(fields, ["a", "b"]);
+    }
+
+    #[actix_web::test]
+    async fn test_validate_graphs() {
+        let _database = DATABASE.read().await;
+
+        let uuid = "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0012";
+        let app = test_app(AppState::default()).await;
+
+        let body = post_body("https://dataset.corrupt");
+        let jsonld = body["jsonld_assessment"].as_str().unwrap().to_string();
+        let req = post_request(uuid, body).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+        // Posts with graphs that do not parse are refused, so the corruption is
+        // written directly, like data stored before posts were validated.
+        let mut conn = PgPool::new().unwrap().get(QueryCount::default()).unwrap();
+        assert!(conn
+            .update_graphs(
+                Uuid::parse_str(uuid).unwrap(),
+                "<https://dataset.corrupt> is not turtle",
+                &jsonld,
+                GraphStorage::Inline,
+            )
+            .unwrap());
+
+        let valid = "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0013";
+        let req = post_request(valid, post_body("https://dataset.valid")).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        let req = test::TestRequest::post()
+            .insert_header(("X-API-KEY", "foo"))
+            .uri("/api/admin/validate-graphs")
+            .to_request();
+        let body: Value = test::call_and_read_body_json(&app, req).await;
+        assert!(body["checked"].as_i64().unwrap() >= 1);
+
+        let invalid: Vec<&Value> = body["invalid"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|graph| graph["id"] == uuid)
+            .collect();
+        assert_eq!(invalid.len(), 1);
+        assert_eq!(invalid[0]["format"], "turtle");
+        assert!(invalid[0]["error"].is_string());
+        assert!(!body["invalid"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|graph| graph["id"] == valid));
+    }
+
+    #[actix_web::test]
+    async fn test_response_signature() {
+        let _database = DATABASE.read().await;
+
+        let uuid = "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0018";
+        let app = test_app(AppState::default()).await;
+
+        let req = post_request(uuid, post_body("https://dataset.signed")).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        for req in [
+            test::TestRequest::post()
+                .set_json(serde_json::json!({ "datasets": ["https://dataset.signed"] }))
+                .uri("/api/scores"),
+            test::TestRequest::get().uri(&format!("/api/assessments/{uuid}")),
+        ] {
+            let resp = test::call_service(&app, req.to_request()).await;
+            assert!(resp.status().is_success());
+            let signature = resp.headers().get("X-Signature").unwrap().clone();
+            let body = test::read_body(resp).await;
+            assert_eq!(signature, signing::signature(b"bar", &body));
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_scores_from_graph() {
+        let _database = DATABASE.read().await;
+
+        let app = test_app(AppState::default()).await;
+
+        // The graph fixture is computed on https://dataset.foo, so it is moved along.
+        let mut body = post_body("https://dataset.graph");
+        body["turtle_assessment"] = Value::from(
+            body["turtle_assessment"]
+                .as_str()
+                .unwrap()
+                .replace("<https://dataset.foo>", "<https://dataset.graph>"),
+        );
+        let req = post_request("5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d001b", body).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        let mut aggregations = Vec::new();
+        for uri in ["/api/scores", "/api/scores?source=graph"] {
+            let req = test::TestRequest::post()
+                .set_json(serde_json::json!({ "datasets": ["https://dataset.graph"] }))
+                .uri(uri)
+                .to_request();
+            let scored: models::DatasetsScores = test::call_and_read_body_json(&app, req).await;
+            // Only the table source reports the spread, so the averages are compared.
+            let mut averages: Vec<_> = scored
+                .aggregations
+                .into_iter()
+                .map(|aggregate| (aggregate.id, aggregate.score, aggregate.max_score))
+                .collect();
+            averages.sort_by(|a, b| a.0.cmp(&b.0));
+            aggregations.push(averages);
+        }
+        assert_eq!(aggregations[0].len(), 5);
+        assert_eq!(aggregations[0], aggregations[1]);
+
+        let req = test::TestRequest::post()
+            .set_json(serde_json::json!({ "datasets": ["https://dataset.graph"] }))
+            .uri("/api/scores?source=cache")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn test_scores_stats() {
+        let _database = DATABASE.read().await;
+
+        let app = test_app(AppState::default()).await;
+
+        let datasets = [
+            (
+                "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d001f",
+                "https://dataset.stats.a",
+                10,
+            ),
+            (
+                "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0020",
+                "https://dataset.stats.b",
+                20,
+            ),
+            (
+                "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0021",
+                "https://dataset.stats.c",
+                30,
+            ),
+        ];
+        for (uuid, dataset, score) in datasets {
+            let mut body = post_body(dataset);
+            for dimension in body["scores"]["dataset"]["dimensions"]
+                .as_array_mut()
+                .unwrap()
+            {
+                dimension["score"] = Value::from(score);
+                dimension["max_score"] = Value::from(100);
+            }
+            let req = post_request(uuid, body).to_request();
+            assert!(test::call_service(&app, req).await.status().is_success());
+        }
+
+        let req = test::TestRequest::post()
+            .set_json(serde_json::json!({
+                "datasets": datasets.iter().map(|(_, dataset, _)| dataset).collect::<Vec<_>>()
+            }))
+            .uri("/api/scores?stats=ci")
+            .to_request();
+        let body: models::DatasetsStatsScores = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body.scores.len(), 3);
+        assert_eq!(body.aggregations.len(), 5);
+        for dimension in body.aggregations {
+            assert_eq!(dimension.count, 3);
+            assert_eq!(dimension.mean, 20.0);
+            assert!((dimension.ci_lower.unwrap() - 8.684).abs() < 1e-3);
+            assert!((dimension.ci_upper.unwrap() - 31.316).abs() < 1e-3);
+        }
+
+        let accessibility = "https://data.norge.no/vocabulary/dcatno-mqa#accessibility";
+        let req = test::TestRequest::post()
+            .set_json(serde_json::json!({
+                "datasets": datasets.iter().map(|(_, dataset, _)| dataset).collect::<Vec<_>>(),
+                "dimensions": [accessibility]
+            }))
+            .uri("/api/scores?stats=ci")
+            .to_request();
+        let body: models::DatasetsStatsScores = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body.aggregations.len(), 1);
+        assert_eq!(body.aggregations[0].id, accessibility);
+
+        for (uri, body) in [
+            (
+                "/api/scores?stats=ci&as_percent=true",
+                serde_json::json!({ "datasets": ["https://dataset.stats.a"] }),
+            ),
+            (
+                "/api/scores?stats=ci&source=graph",
+                serde_json::json!({ "datasets": ["https://dataset.stats.a"] }),
+            ),
+            (
+                "/api/scores?stats=ci&weight_by=distributions",
+                serde_json::json!({ "datasets": ["https://dataset.stats.a"] }),
+            ),
+            (
+                "/api/scores?stats=ci",
+                serde_json::json!({ "datasets": ["https://dataset.stats.a"], "aggregation": "median" }),
+            ),
+        ] {
+            let req = test::TestRequest::post()
+                .set_json(body)
+                .uri(uri)
+                .to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "{uri}");
+            let body: Value = test::read_body_json(resp).await;
+            assert_eq!(body["errors"][0]["field"], "stats");
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_scores_protobuf() {
+        let _database = DATABASE.read().await;
+
+        let app = test_app(AppState::default()).await;
+
+        let req = post_request(
+            "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0014",
+            post_body("https://dataset.protobuf"),
+        )
+        .to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        let req = test::TestRequest::post()
+            .set_json(serde_json::json!({ "datasets": ["https://dataset.protobuf"] }))
+            .uri("/api/scores")
+            .to_request();
+        let mut json: models::DatasetsScores = test::call_and_read_body_json(&app, req).await;
+        // The ratio is only serialized, so it is filled in again after reading the json.
+        json.scores
+            .values_mut()
+            .for_each(|score| aggregation::fill_ratios(score, *OVERALL_STRATEGY));
+
+        let req = test::TestRequest::post()
+            .insert_header((header::ACCEPT, "application/x-protobuf"))
+            .set_json(serde_json::json!({ "datasets": ["https://dataset.protobuf"] }))
+            .uri("/api/scores")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(
+            resp.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/x-protobuf"
+        );
+        let mut decoded = proto::DatasetsScores::decode(test::read_body(resp).await).unwrap();
+
+        let mut expected = proto::DatasetsScores::from(json);
+        for decoded in [&mut decoded, &mut expected] {
+            decoded.aggregations.sort_by(|a, b| a.id.cmp(&b.id));
+        }
+        assert_eq!(decoded, expected);
+
+        let dataset = decoded.scores["https://dataset.protobuf"]
+            .dataset
+            .as_ref()
+            .unwrap();
+        assert_eq!(dataset.id, "https://dataset.protobuf");
+        assert_eq!(dataset.dimensions[0].score, 70);
+        assert_eq!(dataset.dimensions[0].ratio, 0.7);
+        assert!(!decoded.timestamps["https://dataset.protobuf"]
+            .updated_at
+            .is_empty());
+    }
+
+    #[actix_web::test]
+    async fn test_reset() {
+        let _database = DATABASE.write().await;
+
+        let uuid = "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0015";
+        let app = test_app(AppState::default()).await;
+
+        let req = post_request(uuid, post_body("https://dataset.reset")).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        let req = test::TestRequest::post()
+            .uri("/api/admin/reset")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        let req = test::TestRequest::post()
+            .insert_header(("X-API-KEY", "foo"))
+            .uri("/api/admin/reset")
+            .to_request();
+        let body: Value = test::call_and_read_body_json(&app, req).await;
+        assert!(body["assessments"].as_i64().unwrap() >= 1);
+        assert!(body["dimensions"].as_i64().unwrap() >= 5);
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/api/assessments/{uuid}"))
+            .to_request();
+        assert_eq!(
+            test::call_service(&app, req).await.status(),
+            StatusCode::NOT_FOUND
+        );
+        let req = test::TestRequest::get()
+            .uri("/api/dimensions/overview")
+            .to_request();
+        let body: Vec<Value> = test::call_and_read_body_json(&app, req).await;
+        assert!(body.is_empty());
+    }
+
+    #[actix_web::test]
+    async fn test_prune_orphans() {
+        let _database = DATABASE.write().await;
+
+        let app = test_app(AppState::default()).await;
+
+        let req = post_request(
+            "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0037",
+            post_body("https://dataset.not.orphan"),
+        )
+        .to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        // The foreign key cascade keeps dimensions from outliving their assessment,
+        // so the orphan is inserted with constraint triggers off, like a manual edit.
+        let mut raw = PgConnection::establish(&database::database_url().unwrap()).unwrap();
+        diesel::sql_query("SET session_replication_role = replica")
+            .execute(&mut raw)
+            .unwrap();
+        diesel::sql_query(
+            "INSERT INTO dimensions (dataset_uri, id, score, max_score)
+             VALUES ('https://dataset.orphan', 'https://data.norge.no/vocabulary/dcatno-mqa#accessibility', 10, 100)
+             ON CONFLICT DO NOTHING",
+        )
+        .execute(&mut raw)
+        .unwrap();
+
+        let req = test::TestRequest::post()
+            .uri("/api/admin/prune-orphans")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+        let req = test::TestRequest::post()
+            .insert_header(("X-API-KEY", "foo"))
+            .uri("/api/admin/prune-orphans")
+            .to_request();
+        let body: Value = test::call_and_read_body_json(&app, req).await;
+        assert!(body["dimensions"].as_i64().unwrap() >= 1);
 
-    match result {
-        Ok(graph) => Ok(HttpResponse::Ok()
-            .content_type(if accept_json_ld {
-                "application/ld+json"
-            } else {
-                "text/turtle"
-            })
-            .message_body(graph)),
-        Err(e) => Err(e.into()),
+        let mut conn = PgPool::new().unwrap().get(QueryCount::default()).unwrap();
+        let mut aggregates = |dataset: &str| {
+            conn.dimension_aggregates(
+                &[dataset.to_string()],
+                &[],
+                Aggregation::Mean,
+                WeightBy::Datasets,
+            )
+            .unwrap()
+        };
+        assert!(aggregates("https://dataset.orphan").is_empty());
+        assert_eq!(aggregates("https://dataset.not.orphan").len(), 5);
     }
-}
 
-#[post("/api/assessments/{id}")]
-async fn update_assessment(
-    request: HttpRequest,
-    body: web::Bytes,
-    id: web::Path<String>,
-    pool: web::Data<PgPool>,
-) -> Result<impl Responder, Error> {
-    validate_api_key(request)?;
-    let uuid = parse_uuid(id.into_inner())?;
-    let update: models::ScorePostRequest = serde_json::from_str(from_utf8(&body)?)?;
-    let dataset_uri = update.scores.as_ref().dataset.id.clone();
+    #[actix_web::test]
+    async fn test_reset_refused_in_production() {
+        assert!(matches!(
+            ensure_not_production("production"),
+            Err(Error::Forbidden(_))
+        ));
+        assert!(matches!(
+            ensure_not_production("prod"),
+            Err(Error::Forbidden(_))
+        ));
+        assert!(ensure_not_production("staging").is_ok());
+        assert!(ensure_not_production("test").is_ok());
+    }
 
-    let result: Result<(), DatabaseError> = web::block(move || {
-        // Obtaining a connection from the pool is also a potentially blocking operation.
-        // So, it should be called within the `web::block` closure, as well.
-        let mut conn = pool.get()?;
+    #[actix_web::test]
+    async fn test_dimension_trend() {
+        let _database = DATABASE.read().await;
 
-        let assessment = DatasetAssessment {
-            id: uuid.to_string(),
-            dataset_uri: dataset_uri.clone(),
-            turtle_assessment: update.turtle_assessment.clone(),
-            jsonld_assessment: update.jsonld_assessment.clone(),
-            json_score: serde_json::to_string(&update.scores)?,
-        };
+        let uuid = Uuid::new_v4().to_string();
+        let dataset = format!("https://dataset.trend/{uuid}");
+        let path = format!(
+            "/api/assessments/{uuid}/dimensions/{}/trend",
+            "https:%2F%2Fdata.norge.no%2Fvocabulary%2Fdcatno-mqa%23accessibility"
+        );
+        let app = test_app(AppState::default()).await;
 
-        conn.drop_dataset_dimensions(&dataset_uri)?;
-        conn.store_dataset(assessment)?;
+        let req = post_request(&uuid, post_body(&dataset)).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
 
-        for dimension in &update.scores.dataset.dimensions {
-            conn.store_dimension(Dimension {
-                dataset_uri: dataset_uri.clone(),
-                id: dimension.id.clone(),
-                score: dimension.score as i32,
-                max_score: dimension.max_score as i32,
-            })?;
-        }
+        let req = test::TestRequest::get().uri(&path).to_request();
+        let body: Vec<Value> = test::call_and_read_body_json(&app, req).await;
+        assert!(body.is_empty());
 
-        Ok(())
-    })
-    .await
-    .map_err(|e| Error::BlockingError(e.into()))?;
+        let mut update = post_body(&dataset);
+        update["scores"]["dataset"]["dimensions"][0]["score"] = Value::from(35);
+        let req = post_request(&uuid, update).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
 
-    match result {
-        Ok(_) => Ok(HttpResponse::Accepted()
-            .content_type(mime::APPLICATION_JSON)
-            .message_body("")),
-        Err(e) => Err(e.into()),
+        let req = test::TestRequest::get().uri(&path).to_request();
+        let body: Vec<Value> = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body.len(), 2);
+        assert_eq!(body[0]["ratio"], 0.7);
+        assert_eq!(body[1]["ratio"], 0.35);
+        assert!(
+            DateTime::parse_from_rfc3339(body[0]["updated_at"].as_str().unwrap()).unwrap()
+                <= DateTime::parse_from_rfc3339(body[1]["updated_at"].as_str().unwrap()).unwrap()
+        );
     }
-}
 
-#[post("/api/scores")]
-async fn scores(pool: web::Data<PgPool>, body: web::Bytes) -> Result<impl Responder, Error> {
-    let data = serde_json::from_str::<DatasetsRequest>(from_utf8(&body)?)?;
-    // Check that uris are valid, but disregard parsed value.
-    let _parsed_dataset_uris = data
-        .datasets
-        .iter()
-        .map(|uri| uri.parse::<Uri>())
-        .collect::<Result<Vec<Uri>, InvalidUri>>()?;
+    #[actix_web::test]
+    async fn test_duplicate_dimensions() {
+        let app = test_app(AppState::default()).await;
 
-    let result: Result<DatasetsScores, DatabaseError> = web::block(move || {
-        // Obtaining a connection from the pool is also a potentially blocking operation.
-        // So, it should be called within the `web::block` closure, as well.
-        let mut conn = pool.get()?;
+        let mut body = post_body("https://dataset.duplicate");
+        let dimensions = body["scores"]["dataset"]["dimensions"]
+            .as_array_mut()
+            .unwrap();
+        dimensions.push(dimensions[0].clone());
+        let req = post_request("5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0016", body).to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
 
-        Ok(models::DatasetsScores {
-            scores: conn.json_scores(&data.datasets)?,
-            aggregations: conn.dimension_aggregates(&data.datasets)?,
-        })
-    })
-    .await
-    .map_err(|e| Error::BlockingError(e.into()))?;
+        let body: Value = test::read_body_json(resp).await;
+        assert_eq!(body["error"], "duplicate dimension ids");
+        assert_eq!(body["errors"].as_array().unwrap().len(), 1);
+        assert_eq!(
+            body["errors"][0]["field"],
+            "scores.dataset.dimensions[5].id"
+        );
+    }
 
-    match result {
-        Ok(scores) => Ok(HttpResponse::Ok()
-            .content_type(mime::APPLICATION_JSON)
-            .message_body(serde_json::to_string(&scores)?)),
-        Err(e) => Err(e.into()),
+    #[actix_web::test]
+    async fn test_score_out_of_range() {
+        let app = test_app(AppState::default()).await;
+
+        let mut body = post_body("https://dataset.overflow");
+        body["scores"]["dataset"]["dimensions"][0]["score"] = Value::from(i32::MAX as i64 + 1);
+        let req = post_request("5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d001c", body).to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let body: Value = test::read_body_json(resp).await;
+        assert_eq!(body["error"], "scores out of range");
+        assert_eq!(body["errors"].as_array().unwrap().len(), 1);
+        assert_eq!(
+            body["errors"][0]["field"],
+            "scores.dataset.dimensions[0].score"
+        );
     }
-}
 
-#[post("/api/assessments")]
-async fn assessments(
-    accept: web::Header<header::Accept>,
-    pool: web::Data<PgPool>,
-    body: web::Bytes,
-) -> Result<impl Responder, Error> {
-    let data = serde_json::from_str::<DatasetsRequest>(from_utf8(&body)?)?;
-    // Check that uris are valid, but disregard parsed value.
-    let _parsed_dataset_uris = data
-        .datasets
-        .iter()
-        .map(|uri| uri.parse::<Uri>())
-        .collect::<Result<Vec<Uri>, InvalidUri>>()?;
-    let accept_json_ld = accept
-        .0
-        .iter()
-        .any(|qi| qi.item.to_string() == "application/ld+json");
+    #[actix_web::test]
+    async fn test_bulk_update_assessments() {
+        let _database = DATABASE.read().await;
 
-    let result: Result<String, DatabaseError> = web::block(move || {
-        // Obtaining a connection from the pool is also a potentially blocking operation.
-        // So, it should be called within the `web::block` closure, as well.
-        let mut _conn = pool.get()?;
+        let app = test_app(AppState::default()).await;
 
-        if accept_json_ld {
-            // TODO: fetch graphs in jsonld format
-            Ok("".to_string())
-        } else {
-            // TODO: fetch graphs in turtle format
-            Ok("".to_string())
+        let valid = "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d00a1";
+        let invalid = "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d00a2";
+        let stored_uri = "https://dataset.bulk.stored";
+        let req = post_request(
+            "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d00a6",
+            post_body(stored_uri),
+        )
+        .to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+        // A new id for a dataset stored under another one.
+        let conflicting = Uuid::new_v4().to_string();
+        let mut conflicting_item = post_body(stored_uri);
+        conflicting_item["id"] = Value::from(conflicting.as_str());
+        let mut valid_item = post_body("https://dataset.bulk.valid");
+        valid_item["id"] = Value::from(valid);
+        let mut invalid_item = post_body("https://dataset.bulk.invalid");
+        invalid_item["id"] = Value::from(invalid);
+        invalid_item["scores"]["dataset"]["dimensions"][1]["score"] = Value::from(21);
+        let bulk_request = |items: Value| {
+            test::TestRequest::post()
+                .insert_header(("X-API-KEY", "foo"))
+                .set_json(items)
+                .uri("/api/assessments/bulk")
+                .to_request()
+        };
+
+        let results: Vec<models::BulkScoreResult> = test::call_and_read_body_json(
+            &app,
+            bulk_request(serde_json::json!([
+                valid_item,
+                invalid_item,
+                { "id": "not a uuid" },
+                conflicting_item,
+            ])),
+        )
+        .await;
+        assert_eq!(results.len(), 4);
+        assert_eq!(
+            results[0],
+            models::BulkScoreResult::new(valid.to_string(), 202)
+        );
+        assert_eq!(results[1].id, invalid);
+        assert_eq!(results[1].status, 400);
+        assert_eq!(
+            results[1].errors.as_ref().unwrap()[0].field,
+            "scores.dataset.dimensions[1].score"
+        );
+        assert_eq!(results[2].status, 400);
+        assert!(results[2].error.is_some());
+        assert_eq!(results[3].id, conflicting);
+        assert_eq!(results[3].status, 409);
+        assert_eq!(
+            results[3].errors.as_ref().unwrap()[0].field,
+            "scores.dataset.id"
+        );
+
+        for (uuid, status) in [
+            (valid, StatusCode::OK),
+            (invalid, StatusCode::NOT_FOUND),
+            (conflicting.as_str(), StatusCode::NOT_FOUND),
+        ] {
+            let req = test::TestRequest::get()
+                .uri(&format!("/api/assessments/{uuid}/score"))
+                .to_request();
+            assert_eq!(test::call_service(&app, req).await.status(), status);
         }
-    })
-    .await
-    .map_err(|e| Error::BlockingError(e.into()))?;
 
-    match result {
-        Ok(graph) => Ok(HttpResponse::Ok()
-            .content_type(if accept_json_ld {
-                "application/ld+json"
-            } else {
-                "text/turtle"
-            })
-            .message_body(graph)),
-        Err(e) => Err(e.into()),
+        // Posting the same content again stores nothing.
+        let results: Vec<models::BulkScoreResult> =
+            test::call_and_read_body_json(&app, bulk_request(serde_json::json!([valid_item])))
+                .await;
+        assert_eq!(results[0].status, 200);
     }
-}
 
-fn cors() -> Cors {
-    let origins: Vec<String> = ALLOWED_ORIGINS
-        .split(',')
-        .map(|s| s.trim().to_string())
-        .collect();
+    #[actix_web::test]
+    async fn test_score_exceeding_max_score() {
+        let app = test_app(AppState::default()).await;
 
-    Cors::default()
-        .allowed_origin_fn(move |origin, _req_head| {
-            let origin_str = origin.to_str().unwrap_or_default();
+        let mut body = post_body("https://dataset.over.max");
+        body["scores"]["dataset"]["dimensions"][1]["score"] = Value::from(21);
+        let req = post_request("5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0097", body).to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
 
-            for allowed in &origins {
-                if allowed == origin_str {
-                    return true;
-                }
+        let body: Value = test::read_body_json(resp).await;
+        assert_eq!(body["error"], "dimension scores exceeding their max score");
+        assert_eq!(body["errors"].as_array().unwrap().len(), 1);
+        assert_eq!(
+            body["errors"][0]["field"],
+            "scores.dataset.dimensions[1].score"
+        );
+    }
 
-                if allowed.starts_with("*.")
-                    && origin_str.ends_with(allowed.trim_start_matches('*'))
-                {
-                    return true;
-                }
+    #[actix_web::test]
+    async fn test_zero_max_score() {
+        let app = test_app(AppState::default()).await;
 
-                if allowed.ends_with(":*") && origin_str.starts_with(allowed.trim_end_matches(":*"))
-                {
-                    return true;
-                }
-            }
+        let mut body = post_body("https://dataset.zero.max");
+        body["scores"]["distributions"][1]["dimensions"][0]["max_score"] = Value::from(0);
+        let req = post_request("5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0098", body).to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
 
-            false
-        })
-        .allowed_methods(["GET", "POST"])
-        .allow_any_header()
-        .max_age(3600)
-}
+        let body: Value = test::read_body_json(resp).await;
+        assert_eq!(body["errors"].as_array().unwrap().len(), 1);
+        assert_eq!(
+            body["errors"][0]["field"],
+            "scores.distributions[1].dimensions[0].max_score"
+        );
+    }
 
-fn app() -> App<
-    impl ServiceFactory<
-        ServiceRequest,
-        Response = ServiceResponse<EitherBody<BoxBody>>,
-        Error = actix_web::Error,
-        Config = (),
-        InitError = (),
-    >,
-> {
-    let pool = PgPool::new().unwrap();
+    #[actix_web::test]
+    async fn test_dedup_dimensions_policies() {
+        let dimensions = || {
+            vec![
+                models::Dimension::new("a".to_string(), vec![], 1, 10),
+                models::Dimension::new("b".to_string(), vec![], 2, 10),
+                models::Dimension::new("a".to_string(), vec![], 3, 10),
+            ]
+        };
+        let id_scores = |dimensions: &[models::Dimension]| {
+            dimensions
+                .iter()
+                .map(|d| (d.id.clone(), d.score))
+                .collect::<Vec<_>>()
+        };
 
-    let openapi = serde_yaml::from_str::<OpenApi>(include_str!("../openapi.yaml")).unwrap();
+        let mut first = dimensions();
+        dedup_dimensions(&mut first, DuplicatePolicy::First).unwrap();
+        assert_eq!(
+            id_scores(&first),
+            [("a".to_string(), 1), ("b".to_string(), 2)]
+        );
 
-    App::new()
-        .wrap(cors())
-        .app_data(web::PayloadConfig::default().limit(8_388_608))
-        .app_data(web::Data::new(pool.clone()))
-        .service(ping)
-        .service(ready)
-        .service(assessment_graph)
-        .service(update_assessment)
-        .service(assessments)
-        .service(scores)
-        .service(SwaggerUi::new("/swagger-ui/{_:.*}").url("/openapi.json", openapi.clone()))
-}
+        let mut last = dimensions();
+        dedup_dimensions(&mut last, DuplicatePolicy::Last).unwrap();
+        assert_eq!(
+            id_scores(&last),
+            [("a".to_string(), 3), ("b".to_string(), 2)]
+        );
 
-#[actix_web::main]
-async fn main() -> std::io::Result<()> {
-    tracing_subscriber::fmt()
-        .json()
-        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
-        .with_target(false)
-        .with_current_span(false)
-        .init();
+        assert!(matches!(
+            dedup_dimensions(&mut dimensions(), DuplicatePolicy::Reject),
+            Err(Error::DuplicateDimensions(_))
+        ));
+        assert_eq!("last".parse(), Ok(DuplicatePolicy::Last));
+        assert!("merge".parse::<DuplicatePolicy>().is_err());
+    }
 
-    tracing::debug!("Tracing initialized");
+    #[actix_web::test]
+    async fn test_page_sizes() {
+        let size = |n| NonZeroU32::new(n).unwrap();
+        let page_sizes = PageSizes::new(size(10), size(20)).unwrap();
+        assert_eq!(page_sizes.page(None, None), Ok((10, 0)));
+        assert_eq!(page_sizes.page(Some(15), Some(5)), Ok((15, 5)));
+        assert_eq!(page_sizes.page(Some(500), None), Ok((20, 0)));
 
-    migrate_database().unwrap();
+        let fields = |result: Result<(i64, i64), Vec<FieldError>>| -> Vec<String> {
+            result.unwrap_err().into_iter().map(|e| e.field).collect()
+        };
+        assert_eq!(fields(page_sizes.page(Some(0), None)), ["limit"]);
+        assert_eq!(
+            fields(page_sizes.page(Some(-3), Some(-1))),
+            ["limit", "offset"]
+        );
 
-    // Fail if API_KEY missing
-    let _ = API_KEY.clone();
+        assert!(PageSizes::new(size(30), size(20)).is_err());
+    }
 
-    HttpServer::new(move || app().wrap(Logger::default()))
-        .bind(("0.0.0.0", 8082))?
-        .run()
-        .await
-}
+    #[actix_web::test]
+    async fn test_score_schema() {
+        let app = test_app(AppState::default()).await;
 
-fn parse_uuid(uuid: String) -> Result<Uuid, Error> {
-    Uuid::parse_str(uuid.as_ref()).map_err(|_| Error::InvalidID(uuid))
-}
+        let req = test::TestRequest::get()
+            .uri("/api/schema/score")
+            .to_request();
+        let schema: Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(schema["title"], "ScorePostRequest");
+        assert!(schema["properties"]["scores"].is_object());
+        assert!(schema["definitions"]["Score"]["properties"]["dimensions"].is_object());
+        assert!(schema["definitions"]["Dimension"]["properties"]["score"].is_object());
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use actix_web::{
-        http::{header::ContentType, header::HeaderValue, StatusCode},
-        test,
-    };
-    use dotenvy::from_filename;
-    use serde_json::Value;
-    use uuid::Uuid;
+    #[actix_web::test]
+    async fn test_trailing_slash() {
+        let _database = DATABASE.read().await;
 
-    async fn test_get_ok(path: &str) {
-        match from_filename(".env.test") {
-            Ok(_) => println!("Successfully loaded .env.test"),
-            Err(err) => println!("Error loading .env.test: {}", err),
-        }
+        let uuid = "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0030";
+        let app = test_app(AppState::default()).await;
+
+        let req = post_request(uuid, post_body("https://dataset.trailing.slash")).to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
 
-        let app = test::init_service(app()).await;
         let req = test::TestRequest::get()
-            .insert_header(ContentType::plaintext())
-            .insert_header(("Origin", "http://localhost:8080"))
-            .uri(path)
+            .insert_header((header::ACCEPT, APPLICATION_LD_JSON))
+            .uri(&format!("/api/assessments/{uuid}/"))
             .to_request();
         let resp = test::call_service(&app, req).await;
         assert!(resp.status().is_success());
+
+        let req = test::TestRequest::get().uri("/swagger-ui/").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_redirection());
+        assert_eq!(
+            resp.headers().get(header::LOCATION).unwrap(),
+            "/swagger-ui/index.html"
+        );
+
+        let req = test::TestRequest::get()
+            .uri("/swagger-ui/index.html")
+            .to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
     }
 
     #[actix_web::test]
-    async fn test_ping() {
-        test_get_ok("/ping").await;
+    async fn test_openapi_yaml() {
+        let app = test_app(AppState::default()).await;
+
+        let req = test::TestRequest::get().uri("/openapi.json").to_request();
+        let json: Value = test::call_and_read_body_json(&app, req).await;
+
+        let req = test::TestRequest::get().uri("/openapi.yaml").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(
+            resp.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/yaml"
+        );
+        let yaml: Value = serde_yaml::from_slice(&test::read_body(resp).await).unwrap();
+
+        let paths = |spec: &Value| -> HashSet<String> {
+            spec["paths"].as_object().unwrap().keys().cloned().collect()
+        };
+        assert!(paths(&json).contains("/api/scores"));
+        assert_eq!(paths(&json), paths(&yaml));
     }
 
     #[actix_web::test]
-    async fn test_ready() {
-        test_get_ok("/ready").await;
+    async fn test_metrics_gauges() {
+        let _database = DATABASE.read().await;
+
+        let gauges = DimensionGauges::default();
+        let app = test_app(AppState {
+            gauges: gauges.clone(),
+            ..Default::default()
+        })
+        .await;
+
+        let req = post_request(
+            "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0022",
+            post_body("https://dataset.gauges"),
+        )
+        .to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        refresh_gauges(PgPool::new().unwrap(), &gauges)
+            .await
+            .unwrap();
+
+        let req = test::TestRequest::get().uri("/metrics").to_request();
+        let bytes = test::call_and_read_body(&app, req).await;
+        let text = from_utf8(&bytes).unwrap();
+        assert!(text.contains("# TYPE mqa_dimension_avg_ratio gauge"));
+        assert!(text.contains(
+            "mqa_dimension_avg_ratio{dimension=\"https://data.norge.no/vocabulary/dcatno-mqa#accessibility\"}"
+        ));
     }
 
     #[actix_web::test]
-    async fn test_404() {
-        match from_filename(".env.test") {
-            Ok(_) => println!("Successfully loaded .env.test"),
-            Err(err) => println!("Error loading .env.test: {}", err),
-        }
+    async fn test_metrics_requests() {
+        let _database = DATABASE.read().await;
 
-        let uuid = Uuid::parse_str("02f09a3f-1624-3b1d-1337-44eff7708208").unwrap();
-        let path = format!("/api/assessments/{}", uuid);
-
-        let app = test::init_service(app()).await;
+        let app = test_app(AppState {
+            request_metrics: Some(RequestMetrics::default()),
+            ..Default::default()
+        })
+        .await;
 
+        let req = test::TestRequest::get().uri("/ping").to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+        let uuid = "5c8a3a62-2a3e-4a0e-9d0e-6f7a3c1d0090";
         let req = test::TestRequest::get()
-            .insert_header(ContentType::plaintext())
-            .insert_header(("Origin", "http://localhost:8080"))
-            .uri(&path)
+            .uri(&format!("/api/assessments/{uuid}"))
             .to_request();
-        let resp = test::call_service(&app, req).await;
-        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            test::call_service(&app, req).await.status(),
+            StatusCode::NOT_FOUND
+        );
+
+        // No API key is needed.
+        let req = test::TestRequest::get().uri("/metrics").to_request();
+        let bytes = test::call_and_read_body(&app, req).await;
+        let text = from_utf8(&bytes).unwrap();
+        assert!(text.contains("# TYPE mqa_http_requests_total counter"));
+        assert!(text.contains(
+            "mqa_http_requests_total{method=\"GET\",route=\"/ping\",status=\"200\"} 1\n"
+        ));
+        assert!(text.contains(
+            "mqa_http_requests_total{method=\"GET\",route=\"/api/assessments/{id}\",status=\"404\"} 1\n"
+        ));
+        assert!(text.contains(
+            "mqa_http_request_duration_seconds_count{method=\"GET\",route=\"/ping\"} 1\n"
+        ));
+        let queries: u64 = text
+            .lines()
+            .find_map(|line| line.strip_prefix("mqa_db_query_duration_seconds_count "))
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(queries >= 2);
     }
 
     #[actix_web::test]
@@ -407,7 +7243,7 @@ mod tests {
             Err(err) => println!("Error loading .env.test: {}", err),
         }
 
-        let app = test::init_service(app()).await;
+        let app = test::init_service(app(AppState::default())).await;
 
         let origins = [
             "https://example.com",
@@ -439,7 +7275,7 @@ mod tests {
             Err(err) => println!("Error loading .env.test: {}", err),
         }
 
-        let app = test::init_service(app()).await;
+        let app = test::init_service(app(AppState::default())).await;
 
         let origins = ["https://exxxample.com"];
 
@@ -462,11 +7298,12 @@ mod tests {
             Ok(_) => println!("Successfully loaded .env.test"),
             Err(err) => println!("Error loading .env.test: {}", err),
         }
+        let _database = DATABASE.read().await;
 
         let uuid = Uuid::parse_str("02f09a3f-1624-3b1d-8409-44eff7708208").unwrap();
         let path = format!("/api/assessments/{}", uuid);
 
-        let app = test::init_service(app()).await;
+        let app = test::init_service(app(AppState::default())).await;
 
         let req = test::TestRequest::post()
             .insert_header(ContentType::json())
@@ -502,7 +7339,8 @@ mod tests {
 
         let req = test::TestRequest::get()
             .insert_header(("Origin", "http://localhost:8080"))
-            .uri(&path).to_request();
+            .uri(&path)
+            .to_request();
         let bytes = test::call_and_read_body(&app, req).await;
         assert_eq!(
             String::from_utf8(bytes.to_vec()).unwrap(),
@@ -524,7 +7362,11 @@ mod tests {
             )
             .uri("/api/scores")
             .to_request();
-        let body: Value = test::call_and_read_body_json(&app, req).await;
+        let mut body: Value = test::call_and_read_body_json(&app, req).await;
+        // Timestamps differ between runs.
+        let timestamps = body.as_object_mut().unwrap().remove("timestamps").unwrap();
+        assert!(timestamps["https://dataset.foo"]["created_at"].is_string());
+        assert!(timestamps["https://dataset.foo"]["updated_at"].is_string());
         assert_eq!(
             body,
             serde_json::from_str::<Value>(include_str!("../tests/score.json")).unwrap()
@@ -0,0 +1,26 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct AggregateWarmup {
+    #[serde(rename = "groups")]
+    pub groups: i64,
+}
+
+impl AggregateWarmup {
+    pub fn new(groups: i64) -> AggregateWarmup {
+        AggregateWarmup {
+            groups,
+        }
+    }
+}
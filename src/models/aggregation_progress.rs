@@ -0,0 +1,29 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct AggregationProgress {
+    #[serde(rename = "processed")]
+    pub processed: i64,
+    #[serde(rename = "total")]
+    pub total: i64,
+}
+
+impl AggregationProgress {
+    pub fn new(processed: i64, total: i64) -> AggregationProgress {
+        AggregationProgress {
+            processed,
+            total,
+        }
+    }
+}
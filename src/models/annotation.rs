@@ -0,0 +1,31 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct Annotation {
+    #[serde(rename = "key")]
+    pub key: String,
+    #[serde(rename = "value")]
+    pub value: String,
+}
+
+impl Annotation {
+    pub fn new(key: String, value: String) -> Annotation {
+        Annotation {
+            key,
+            value,
+        }
+    }
+}
+
+
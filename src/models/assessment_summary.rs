@@ -0,0 +1,34 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct AssessmentSummary {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "dataset_uri")]
+    pub dataset_uri: String,
+    #[serde(rename = "updated_at")]
+    pub updated_at: String,
+}
+
+impl AssessmentSummary {
+    pub fn new(id: String, dataset_uri: String, updated_at: String) -> AssessmentSummary {
+        AssessmentSummary {
+            id,
+            dataset_uri,
+            updated_at,
+        }
+    }
+}
+
+
@@ -0,0 +1,29 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct AssessmentTimestamps {
+    #[serde(rename = "created_at")]
+    pub created_at: String,
+    #[serde(rename = "updated_at")]
+    pub updated_at: String,
+}
+
+impl AssessmentTimestamps {
+    pub fn new(created_at: String, updated_at: String) -> AssessmentTimestamps {
+        AssessmentTimestamps {
+            created_at,
+            updated_at,
+        }
+    }
+}
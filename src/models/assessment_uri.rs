@@ -0,0 +1,37 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct AssessmentUri {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "dataset_uri")]
+    pub dataset_uri: String,
+    #[serde(rename = "assessment_count")]
+    pub assessment_count: i64,
+    #[serde(rename = "duplicate_uri")]
+    pub duplicate_uri: bool,
+}
+
+impl AssessmentUri {
+    pub fn new(id: String, dataset_uri: String, assessment_count: i64, duplicate_uri: bool) -> AssessmentUri {
+        AssessmentUri {
+            id,
+            dataset_uri,
+            assessment_count,
+            duplicate_uri,
+        }
+    }
+}
+
+
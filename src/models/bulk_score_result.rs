@@ -0,0 +1,38 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct BulkScoreResult {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "status")]
+    pub status: i32,
+    #[serde(rename = "error", skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(rename = "errors", skip_serializing_if = "Option::is_none")]
+    pub errors: Option<Vec<crate::models::FieldError>>,
+    #[serde(rename = "warnings", skip_serializing_if = "Option::is_none")]
+    pub warnings: Option<Vec<crate::models::FieldError>>,
+}
+
+impl BulkScoreResult {
+    pub fn new(id: String, status: i32) -> BulkScoreResult {
+        BulkScoreResult {
+            id,
+            status,
+            error: None,
+            errors: None,
+            warnings: None,
+        }
+    }
+}
@@ -0,0 +1,32 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct CatalogComparison {
+    #[serde(rename = "a")]
+    pub a: String,
+    #[serde(rename = "b")]
+    pub b: String,
+    #[serde(rename = "dimensions")]
+    pub dimensions: Vec<crate::models::DimensionComparison>,
+}
+
+impl CatalogComparison {
+    pub fn new(a: String, b: String, dimensions: Vec<crate::models::DimensionComparison>) -> CatalogComparison {
+        CatalogComparison {
+            a,
+            b,
+            dimensions,
+        }
+    }
+}
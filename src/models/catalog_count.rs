@@ -0,0 +1,32 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct CatalogCount {
+    #[serde(rename = "catalog_uri")]
+    pub catalog_uri: String,
+    #[serde(rename = "assessment_count")]
+    pub assessment_count: i64,
+    #[serde(rename = "avg_overall_ratio")]
+    pub avg_overall_ratio: f64,
+}
+
+impl CatalogCount {
+    pub fn new(catalog_uri: String, assessment_count: i64, avg_overall_ratio: f64) -> CatalogCount {
+        CatalogCount {
+            catalog_uri,
+            assessment_count,
+            avg_overall_ratio,
+        }
+    }
+}
@@ -0,0 +1,34 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct DatasetComparison {
+    #[serde(rename = "a")]
+    pub a: String,
+    #[serde(rename = "b")]
+    pub b: String,
+    #[serde(rename = "dimensions")]
+    pub dimensions: Vec<crate::models::DatasetDimensionComparison>,
+}
+
+impl DatasetComparison {
+    pub fn new(a: String, b: String, dimensions: Vec<crate::models::DatasetDimensionComparison>) -> DatasetComparison {
+        DatasetComparison {
+            a,
+            b,
+            dimensions,
+        }
+    }
+}
+
+
@@ -0,0 +1,31 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct DatasetComparisonRequest {
+    #[serde(rename = "a")]
+    pub a: String,
+    #[serde(rename = "b")]
+    pub b: String,
+}
+
+impl DatasetComparisonRequest {
+    pub fn new(a: String, b: String) -> DatasetComparisonRequest {
+        DatasetComparisonRequest {
+            a,
+            b,
+        }
+    }
+}
+
+
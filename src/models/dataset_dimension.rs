@@ -0,0 +1,32 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct DatasetDimension {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "score")]
+    pub score: i32,
+    #[serde(rename = "max_score")]
+    pub max_score: i32,
+}
+
+impl DatasetDimension {
+    pub fn new(id: String, score: i32, max_score: i32) -> DatasetDimension {
+        DatasetDimension {
+            id,
+            score,
+            max_score,
+        }
+    }
+}
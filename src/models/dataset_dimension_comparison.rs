@@ -0,0 +1,43 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct DatasetDimensionComparison {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "a_score", skip_serializing_if = "Option::is_none")]
+    pub a_score: Option<i32>,
+    #[serde(rename = "a_max_score", skip_serializing_if = "Option::is_none")]
+    pub a_max_score: Option<i32>,
+    #[serde(rename = "b_score", skip_serializing_if = "Option::is_none")]
+    pub b_score: Option<i32>,
+    #[serde(rename = "b_max_score", skip_serializing_if = "Option::is_none")]
+    pub b_max_score: Option<i32>,
+    #[serde(rename = "delta", skip_serializing_if = "Option::is_none")]
+    pub delta: Option<i32>,
+}
+
+impl DatasetDimensionComparison {
+    pub fn new(id: String) -> DatasetDimensionComparison {
+        DatasetDimensionComparison {
+            id,
+            a_score: None,
+            a_max_score: None,
+            b_score: None,
+            b_max_score: None,
+            delta: None,
+        }
+    }
+}
+
+
@@ -0,0 +1,32 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct DatasetDimensionScore {
+    #[serde(rename = "dataset_uri")]
+    pub dataset_uri: String,
+    #[serde(rename = "score")]
+    pub score: i32,
+    #[serde(rename = "max_score")]
+    pub max_score: i32,
+}
+
+impl DatasetDimensionScore {
+    pub fn new(dataset_uri: String, score: i32, max_score: i32) -> DatasetDimensionScore {
+        DatasetDimensionScore {
+            dataset_uri,
+            score,
+            max_score,
+        }
+    }
+}
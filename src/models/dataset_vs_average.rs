@@ -0,0 +1,31 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct DatasetVsAverage {
+    #[serde(rename = "dataset")]
+    pub dataset: String,
+    #[serde(rename = "dimensions")]
+    pub dimensions: Vec<crate::models::DimensionVsAverage>,
+}
+
+impl DatasetVsAverage {
+    pub fn new(dataset: String, dimensions: Vec<crate::models::DimensionVsAverage>) -> DatasetVsAverage {
+        DatasetVsAverage {
+            dataset,
+            dimensions,
+        }
+    }
+}
+
+
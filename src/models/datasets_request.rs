@@ -15,12 +15,30 @@
 pub struct DatasetsRequest {
     #[serde(rename = "datasets")]
     pub datasets: Vec<String>,
+    #[serde(rename = "dimension_thresholds", skip_serializing_if = "Option::is_none")]
+    pub dimension_thresholds: Option<std::collections::HashMap<String, f64>>,
+    #[serde(rename = "aggregation", skip_serializing_if = "Option::is_none")]
+    pub aggregation: Option<String>,
+    #[serde(rename = "percentiles", skip_serializing_if = "Option::is_none")]
+    pub percentiles: Option<Vec<f64>>,
+    #[serde(rename = "exclude", skip_serializing_if = "Option::is_none")]
+    pub exclude: Option<Vec<String>>,
+    #[serde(rename = "dimensions", skip_serializing_if = "Option::is_none")]
+    pub dimensions: Option<Vec<String>>,
+    #[serde(rename = "buckets", skip_serializing_if = "Option::is_none")]
+    pub buckets: Option<i32>,
 }
 
 impl DatasetsRequest {
     pub fn new(datasets: Vec<String>) -> DatasetsRequest {
         DatasetsRequest {
             datasets,
+            dimension_thresholds: None,
+            aggregation: None,
+            percentiles: None,
+            exclude: None,
+            dimensions: None,
+            buckets: None,
         }
     }
 }
@@ -17,13 +17,22 @@ pub struct DatasetsScores {
     pub scores: ::std::collections::HashMap<String, crate::models::DatasetScore>,
     #[serde(rename = "aggregations")]
     pub aggregations: Vec<crate::models::DimensionAggregate>,
+    #[serde(rename = "timestamps")]
+    pub timestamps: ::std::collections::HashMap<String, crate::models::AssessmentTimestamps>,
+    #[serde(rename = "dimensions")]
+    pub dimensions: ::std::collections::HashMap<String, Vec<crate::models::DatasetDimension>>,
+    #[serde(rename = "percentiles", skip_serializing_if = "Option::is_none")]
+    pub percentiles: Option<::std::collections::HashMap<String, ::std::collections::HashMap<String, f64>>>,
 }
 
 impl DatasetsScores {
-    pub fn new(scores: ::std::collections::HashMap<String, crate::models::DatasetScore>, aggregations: Vec<crate::models::DimensionAggregate>) -> DatasetsScores {
+    pub fn new(scores: ::std::collections::HashMap<String, crate::models::DatasetScore>, aggregations: Vec<crate::models::DimensionAggregate>, timestamps: ::std::collections::HashMap<String, crate::models::AssessmentTimestamps>, dimensions: ::std::collections::HashMap<String, Vec<crate::models::DatasetDimension>>) -> DatasetsScores {
         DatasetsScores {
             scores,
             aggregations,
+            timestamps,
+            dimensions,
+            percentiles: None,
         }
     }
 }
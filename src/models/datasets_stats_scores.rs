@@ -0,0 +1,29 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct DatasetsStatsScores {
+    #[serde(rename = "scores")]
+    pub scores: ::std::collections::HashMap<String, crate::models::DatasetScore>,
+    #[serde(rename = "aggregations")]
+    pub aggregations: Vec<crate::models::DimensionStats>,
+}
+
+impl DatasetsStatsScores {
+    pub fn new(scores: ::std::collections::HashMap<String, crate::models::DatasetScore>, aggregations: Vec<crate::models::DimensionStats>) -> DatasetsStatsScores {
+        DatasetsStatsScores {
+            scores,
+            aggregations,
+        }
+    }
+}
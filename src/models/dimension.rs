@@ -11,7 +11,7 @@
 
 
 
-#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct Dimension {
     #[serde(rename = "id")]
     pub id: String,
@@ -21,6 +21,8 @@ pub struct Dimension {
     pub score: i32,
     #[serde(rename = "max_score")]
     pub max_score: i32,
+    #[serde(rename = "ratio", default, skip_deserializing)]
+    pub ratio: f64,
 }
 
 impl Dimension {
@@ -30,6 +32,7 @@ impl Dimension {
             metrics,
             score,
             max_score,
+            ratio: 0.0,
         }
     }
 }
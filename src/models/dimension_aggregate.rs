@@ -19,6 +19,16 @@ pub struct DimensionAggregate {
     pub score: f64,
     #[serde(rename = "max_score")]
     pub max_score: f64,
+    #[serde(rename = "ratio", skip_serializing_if = "Option::is_none")]
+    pub ratio: Option<f64>,
+    #[serde(rename = "min_observed_score", skip_serializing_if = "Option::is_none")]
+    pub min_observed_score: Option<f64>,
+    #[serde(rename = "max_observed_score", skip_serializing_if = "Option::is_none")]
+    pub max_observed_score: Option<f64>,
+    #[serde(rename = "min_ratio", skip_serializing_if = "Option::is_none")]
+    pub min_ratio: Option<f64>,
+    #[serde(rename = "max_ratio", skip_serializing_if = "Option::is_none")]
+    pub max_ratio: Option<f64>,
 }
 
 impl DimensionAggregate {
@@ -27,6 +37,11 @@ impl DimensionAggregate {
             id,
             score,
             max_score,
+            ratio: None,
+            min_observed_score: None,
+            max_observed_score: None,
+            min_ratio: None,
+            max_ratio: None,
         }
     }
 }
@@ -0,0 +1,35 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct DimensionComparison {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "a", skip_serializing_if = "Option::is_none")]
+    pub a: Option<f64>,
+    #[serde(rename = "b", skip_serializing_if = "Option::is_none")]
+    pub b: Option<f64>,
+    #[serde(rename = "delta", skip_serializing_if = "Option::is_none")]
+    pub delta: Option<f64>,
+}
+
+impl DimensionComparison {
+    pub fn new(id: String) -> DimensionComparison {
+        DimensionComparison {
+            id,
+            a: None,
+            b: None,
+            delta: None,
+        }
+    }
+}
@@ -0,0 +1,32 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct DimensionDiff {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "previous_score", skip_serializing_if = "Option::is_none")]
+    pub previous_score: Option<i32>,
+    #[serde(rename = "score", skip_serializing_if = "Option::is_none")]
+    pub score: Option<i32>,
+}
+
+impl DimensionDiff {
+    pub fn new(id: String) -> DimensionDiff {
+        DimensionDiff {
+            id,
+            previous_score: None,
+            score: None,
+        }
+    }
+}
@@ -0,0 +1,32 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct DimensionOverview {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "average_ratio")]
+    pub average_ratio: f64,
+    #[serde(rename = "dataset_count")]
+    pub dataset_count: i64,
+}
+
+impl DimensionOverview {
+    pub fn new(id: String, average_ratio: f64, dataset_count: i64) -> DimensionOverview {
+        DimensionOverview {
+            id,
+            average_ratio,
+            dataset_count,
+        }
+    }
+}
@@ -0,0 +1,31 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct DimensionPercentAggregate {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "score_percent")]
+    pub score_percent: f64,
+}
+
+impl DimensionPercentAggregate {
+    pub fn new(id: String, score_percent: f64) -> DimensionPercentAggregate {
+        DimensionPercentAggregate {
+            id,
+            score_percent,
+        }
+    }
+}
+
+
@@ -0,0 +1,44 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct DimensionStats {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "count")]
+    pub count: i64,
+    #[serde(rename = "mean")]
+    pub mean: f64,
+    #[serde(rename = "max_score")]
+    pub max_score: f64,
+    #[serde(rename = "stddev", skip_serializing_if = "Option::is_none")]
+    pub stddev: Option<f64>,
+    #[serde(rename = "ci_lower", skip_serializing_if = "Option::is_none")]
+    pub ci_lower: Option<f64>,
+    #[serde(rename = "ci_upper", skip_serializing_if = "Option::is_none")]
+    pub ci_upper: Option<f64>,
+}
+
+impl DimensionStats {
+    pub fn new(id: String, count: i64, mean: f64, max_score: f64) -> DimensionStats {
+        DimensionStats {
+            id,
+            count,
+            mean,
+            max_score,
+            stddev: None,
+            ci_lower: None,
+            ci_upper: None,
+        }
+    }
+}
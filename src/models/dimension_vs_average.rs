@@ -0,0 +1,37 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct DimensionVsAverage {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "ratio")]
+    pub ratio: f64,
+    #[serde(rename = "average_ratio")]
+    pub average_ratio: f64,
+    #[serde(rename = "delta")]
+    pub delta: f64,
+}
+
+impl DimensionVsAverage {
+    pub fn new(id: String, ratio: f64, average_ratio: f64, delta: f64) -> DimensionVsAverage {
+        DimensionVsAverage {
+            id,
+            ratio,
+            average_ratio,
+            delta,
+        }
+    }
+}
+
+
@@ -0,0 +1,26 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct DimensionsScores {
+    #[serde(rename = "dimensions")]
+    pub dimensions: ::std::collections::HashMap<String, Vec<crate::models::DatasetDimensionScore>>,
+}
+
+impl DimensionsScores {
+    pub fn new(dimensions: ::std::collections::HashMap<String, Vec<crate::models::DatasetDimensionScore>>) -> DimensionsScores {
+        DimensionsScores {
+            dimensions,
+        }
+    }
+}
@@ -17,6 +17,8 @@ pub struct Error {
     pub message: Option<String>,
     #[serde(rename = "error", skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    #[serde(rename = "errors", skip_serializing_if = "Option::is_none")]
+    pub errors: Option<Vec<crate::models::FieldError>>,
 }
 
 impl Error {
@@ -24,6 +26,7 @@ impl Error {
         Error {
             message: None,
             error: None,
+            errors: None,
         }
     }
 }
@@ -0,0 +1,31 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct FieldError {
+    #[serde(rename = "field")]
+    pub field: String,
+    #[serde(rename = "message")]
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: String, message: String) -> FieldError {
+        FieldError {
+            field,
+            message,
+        }
+    }
+}
+
+
@@ -0,0 +1,29 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct GraphValidationReport {
+    #[serde(rename = "checked")]
+    pub checked: i64,
+    #[serde(rename = "invalid")]
+    pub invalid: Vec<crate::models::InvalidGraph>,
+}
+
+impl GraphValidationReport {
+    pub fn new(checked: i64, invalid: Vec<crate::models::InvalidGraph>) -> GraphValidationReport {
+        GraphValidationReport {
+            checked,
+            invalid,
+        }
+    }
+}
@@ -0,0 +1,32 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct InvalidGraph {
+    #[serde(rename = "id")]
+    pub id: String,
+    #[serde(rename = "format")]
+    pub format: String,
+    #[serde(rename = "error")]
+    pub error: String,
+}
+
+impl InvalidGraph {
+    pub fn new(id: String, format: String, error: String) -> InvalidGraph {
+        InvalidGraph {
+            id,
+            format,
+            error,
+        }
+    }
+}
@@ -1,22 +1,100 @@
 pub mod _api_assessments__uuid__get_200_response_inner;
 pub use self::_api_assessments__uuid__get_200_response_inner::ApiAssessmentsUuidGet200ResponseInner;
+pub mod aggregate_warmup;
+pub use self::aggregate_warmup::AggregateWarmup;
+pub mod aggregation_progress;
+pub use self::aggregation_progress::AggregationProgress;
+pub mod annotation;
+pub use self::annotation::Annotation;
+pub mod assessment_summary;
+pub use self::assessment_summary::AssessmentSummary;
+pub mod assessment_timestamps;
+pub use self::assessment_timestamps::AssessmentTimestamps;
+pub mod assessment_uri;
+pub use self::assessment_uri::AssessmentUri;
+pub mod bulk_score_result;
+pub use self::bulk_score_result::BulkScoreResult;
+pub mod catalog_comparison;
+pub use self::catalog_comparison::CatalogComparison;
+pub mod catalog_count;
+pub use self::catalog_count::CatalogCount;
+pub mod dataset_comparison;
+pub use self::dataset_comparison::DatasetComparison;
+pub mod dataset_comparison_request;
+pub use self::dataset_comparison_request::DatasetComparisonRequest;
+pub mod dataset_dimension;
+pub use self::dataset_dimension::DatasetDimension;
+pub mod dataset_dimension_comparison;
+pub use self::dataset_dimension_comparison::DatasetDimensionComparison;
+pub mod dataset_dimension_score;
+pub use self::dataset_dimension_score::DatasetDimensionScore;
 pub mod dataset_score;
 pub use self::dataset_score::DatasetScore;
+pub mod dataset_vs_average;
+pub use self::dataset_vs_average::DatasetVsAverage;
 pub mod datasets_graphs;
 pub use self::datasets_graphs::DatasetsGraphs;
+pub mod datasets_percent_scores;
+pub use self::datasets_percent_scores::DatasetsPercentScores;
 pub mod datasets_request;
 pub use self::datasets_request::DatasetsRequest;
 pub mod datasets_scores;
 pub use self::datasets_scores::DatasetsScores;
+pub mod datasets_stats_scores;
+pub use self::datasets_stats_scores::DatasetsStatsScores;
 pub mod dimension;
 pub use self::dimension::Dimension;
 pub mod dimension_aggregate;
 pub use self::dimension_aggregate::DimensionAggregate;
+pub mod dimension_comparison;
+pub use self::dimension_comparison::DimensionComparison;
+pub mod dimension_diff;
+pub use self::dimension_diff::DimensionDiff;
+pub mod dimension_overview;
+pub use self::dimension_overview::DimensionOverview;
+pub mod dimension_percent_aggregate;
+pub use self::dimension_percent_aggregate::DimensionPercentAggregate;
+pub mod dimension_stats;
+pub use self::dimension_stats::DimensionStats;
+pub mod dimension_vs_average;
+pub use self::dimension_vs_average::DimensionVsAverage;
+pub mod dimensions_scores;
+pub use self::dimensions_scores::DimensionsScores;
 pub mod error;
 pub use self::error::Error;
+pub mod field_error;
+pub use self::field_error::FieldError;
+pub mod graph_validation_report;
+pub use self::graph_validation_report::GraphValidationReport;
+pub mod invalid_graph;
+pub use self::invalid_graph::InvalidGraph;
 pub mod metric;
 pub use self::metric::Metric;
+pub mod percentile_rank;
+pub use self::percentile_rank::PercentileRank;
+pub mod percentile_rank_request;
+pub use self::percentile_rank_request::PercentileRankRequest;
+pub mod pool_state;
+pub use self::pool_state::PoolState;
+pub mod pruned_orphans;
+pub use self::pruned_orphans::PrunedOrphans;
+pub mod publisher_aggregate;
+pub use self::publisher_aggregate::PublisherAggregate;
+pub mod recent_error;
+pub use self::recent_error::RecentError;
+pub mod replication_health;
+pub use self::replication_health::ReplicationHealth;
+pub mod reset_counts;
+pub use self::reset_counts::ResetCounts;
 pub mod score;
 pub use self::score::Score;
+pub mod score_histogram;
+pub use self::score_histogram::ScoreHistogram;
 pub mod score_post_request;
 pub use self::score_post_request::ScorePostRequest;
+pub mod score_post_response;
+pub use self::score_post_response::ScorePostResponse;
+pub mod trend_point;
+pub use self::trend_point::TrendPoint;
+pub mod write_freeze_setting;
+pub use self::write_freeze_setting::WriteFreezeSetting;
@@ -0,0 +1,38 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct PercentileRank {
+    #[serde(rename = "dataset_uri")]
+    pub dataset_uri: String,
+    #[serde(rename = "dimension")]
+    pub dimension: String,
+    #[serde(rename = "ratio")]
+    pub ratio: f64,
+    #[serde(rename = "percentile_rank")]
+    pub percentile_rank: f64,
+    #[serde(rename = "ranked_count")]
+    pub ranked_count: i64,
+}
+
+impl PercentileRank {
+    pub fn new(dataset_uri: String, dimension: String, ratio: f64, percentile_rank: f64, ranked_count: i64) -> PercentileRank {
+        PercentileRank {
+            dataset_uri,
+            dimension,
+            ratio,
+            percentile_rank,
+            ranked_count,
+        }
+    }
+}
@@ -0,0 +1,32 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct PercentileRankRequest {
+    #[serde(rename = "dataset_uri")]
+    pub dataset_uri: String,
+    #[serde(rename = "peer_uris")]
+    pub peer_uris: Vec<String>,
+    #[serde(rename = "dimension")]
+    pub dimension: String,
+}
+
+impl PercentileRankRequest {
+    pub fn new(dataset_uri: String, peer_uris: Vec<String>, dimension: String) -> PercentileRankRequest {
+        PercentileRankRequest {
+            dataset_uri,
+            peer_uris,
+            dimension,
+        }
+    }
+}
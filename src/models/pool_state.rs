@@ -0,0 +1,35 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct PoolState {
+    #[serde(rename = "connections")]
+    pub connections: i64,
+    #[serde(rename = "idle_connections")]
+    pub idle_connections: i64,
+    #[serde(rename = "in_use_connections")]
+    pub in_use_connections: i64,
+    #[serde(rename = "max_size")]
+    pub max_size: i64,
+}
+
+impl PoolState {
+    pub fn new(connections: i64, idle_connections: i64, in_use_connections: i64, max_size: i64) -> PoolState {
+        PoolState {
+            connections,
+            idle_connections,
+            in_use_connections,
+            max_size,
+        }
+    }
+}
@@ -0,0 +1,26 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct PrunedOrphans {
+    #[serde(rename = "dimensions")]
+    pub dimensions: i64,
+}
+
+impl PrunedOrphans {
+    pub fn new(dimensions: i64) -> PrunedOrphans {
+        PrunedOrphans {
+            dimensions,
+        }
+    }
+}
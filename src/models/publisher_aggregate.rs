@@ -0,0 +1,29 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct PublisherAggregate {
+    #[serde(rename = "publisher_uri")]
+    pub publisher_uri: String,
+    #[serde(rename = "aggregations")]
+    pub aggregations: Vec<crate::models::DimensionAggregate>,
+}
+
+impl PublisherAggregate {
+    pub fn new(publisher_uri: String, aggregations: Vec<crate::models::DimensionAggregate>) -> PublisherAggregate {
+        PublisherAggregate {
+            publisher_uri,
+            aggregations,
+        }
+    }
+}
@@ -0,0 +1,41 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct RecentError {
+    #[serde(rename = "timestamp")]
+    pub timestamp: String,
+    #[serde(rename = "request_id")]
+    pub request_id: String,
+    #[serde(rename = "method")]
+    pub method: String,
+    #[serde(rename = "path")]
+    pub path: String,
+    #[serde(rename = "status")]
+    pub status: i32,
+    #[serde(rename = "error")]
+    pub error: String,
+}
+
+impl RecentError {
+    pub fn new(timestamp: String, request_id: String, method: String, path: String, status: i32, error: String) -> RecentError {
+        RecentError {
+            timestamp,
+            request_id,
+            method,
+            path,
+            status,
+            error,
+        }
+    }
+}
@@ -0,0 +1,37 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct ReplicationHealth {
+    #[serde(rename = "status")]
+    pub status: String,
+    #[serde(rename = "replica")]
+    pub replica: bool,
+    #[serde(rename = "lag_seconds", skip_serializing_if = "Option::is_none")]
+    pub lag_seconds: Option<f64>,
+    #[serde(rename = "max_lag_seconds")]
+    pub max_lag_seconds: f64,
+}
+
+impl ReplicationHealth {
+    pub fn new(status: String, replica: bool, max_lag_seconds: f64) -> ReplicationHealth {
+        ReplicationHealth {
+            status,
+            replica,
+            lag_seconds: None,
+            max_lag_seconds,
+        }
+    }
+}
+
+
@@ -0,0 +1,29 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct ResetCounts {
+    #[serde(rename = "assessments")]
+    pub assessments: i64,
+    #[serde(rename = "dimensions")]
+    pub dimensions: i64,
+}
+
+impl ResetCounts {
+    pub fn new(assessments: i64, dimensions: i64) -> ResetCounts {
+        ResetCounts {
+            assessments,
+            dimensions,
+        }
+    }
+}
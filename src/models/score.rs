@@ -11,7 +11,7 @@
 
 
 
-#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct Score {
     #[serde(rename = "id")]
     pub id: String,
@@ -21,6 +21,8 @@ pub struct Score {
     pub score: i32,
     #[serde(rename = "max_score")]
     pub max_score: i32,
+    #[serde(rename = "ratio", default, skip_deserializing)]
+    pub ratio: f64,
 }
 
 impl Score {
@@ -30,6 +32,7 @@ impl Score {
             dimensions,
             score,
             max_score,
+            ratio: 0.0,
         }
     }
 }
@@ -0,0 +1,29 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct ScoreHistogram {
+    #[serde(rename = "bucket_edges")]
+    pub bucket_edges: Vec<f64>,
+    #[serde(rename = "counts")]
+    pub counts: Vec<i64>,
+}
+
+impl ScoreHistogram {
+    pub fn new(bucket_edges: Vec<f64>, counts: Vec<i64>) -> ScoreHistogram {
+        ScoreHistogram {
+            bucket_edges,
+            counts,
+        }
+    }
+}
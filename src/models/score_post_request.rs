@@ -11,7 +11,7 @@
 
 
 
-#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct ScorePostRequest {
     #[serde(rename = "turtle_assessment")]
     pub turtle_assessment: String,
@@ -19,6 +19,10 @@ pub struct ScorePostRequest {
     pub jsonld_assessment: String,
     #[serde(rename = "scores")]
     pub scores: Box<crate::models::DatasetScore>,
+    #[serde(rename = "catalog_uri", skip_serializing_if = "Option::is_none")]
+    pub catalog_uri: Option<String>,
+    #[serde(rename = "publisher_uri", skip_serializing_if = "Option::is_none")]
+    pub publisher_uri: Option<String>,
 }
 
 impl ScorePostRequest {
@@ -27,6 +31,8 @@ impl ScorePostRequest {
             turtle_assessment,
             jsonld_assessment,
             scores: Box::new(scores),
+            catalog_uri: None,
+            publisher_uri: None,
         }
     }
 }
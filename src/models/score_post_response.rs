@@ -0,0 +1,31 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct ScorePostResponse {
+    #[serde(rename = "dimensions", skip_serializing_if = "Option::is_none")]
+    pub dimensions: Option<Vec<crate::models::DimensionDiff>>,
+    #[serde(rename = "warnings", skip_serializing_if = "Option::is_none")]
+    pub warnings: Option<Vec<crate::models::FieldError>>,
+}
+
+impl ScorePostResponse {
+    pub fn new() -> ScorePostResponse {
+        ScorePostResponse {
+            dimensions: None,
+            warnings: None,
+        }
+    }
+}
+
+
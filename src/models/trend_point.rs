@@ -0,0 +1,29 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct TrendPoint {
+    #[serde(rename = "updated_at")]
+    pub updated_at: String,
+    #[serde(rename = "ratio")]
+    pub ratio: f64,
+}
+
+impl TrendPoint {
+    pub fn new(updated_at: String, ratio: f64) -> TrendPoint {
+        TrendPoint {
+            updated_at,
+            ratio,
+        }
+    }
+}
@@ -0,0 +1,26 @@
+/*
+ * Metadata Quality
+ *
+ * Metadata quality of datasets
+ *
+ * The version of the OpenAPI document: 0.1.0
+ * 
+ * Generated by: https://openapi-generator.tech
+ */
+
+
+
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct WriteFreezeSetting {
+    #[serde(rename = "until", skip_serializing_if = "Option::is_none")]
+    pub until: Option<String>,
+}
+
+impl WriteFreezeSetting {
+    pub fn new() -> WriteFreezeSetting {
+        WriteFreezeSetting {
+            until: None,
+        }
+    }
+}
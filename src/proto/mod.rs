@@ -0,0 +1,116 @@
+//! Protocol Buffers messages of `proto/scores.proto`, generated with prost-build
+//! and checked in like the OpenAPI models.
+
+use crate::models;
+
+include!("mqa.scores.rs");
+
+impl From<models::DatasetsScores> for DatasetsScores {
+    fn from(scores: models::DatasetsScores) -> Self {
+        DatasetsScores {
+            scores: scores
+                .scores
+                .into_iter()
+                .map(|(dataset_uri, score)| (dataset_uri, score.into()))
+                .collect(),
+            aggregations: scores.aggregations.into_iter().map(Into::into).collect(),
+            timestamps: scores
+                .timestamps
+                .into_iter()
+                .map(|(dataset_uri, timestamps)| (dataset_uri, timestamps.into()))
+                .collect(),
+            percentiles: scores
+                .percentiles
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(id, scores)| (id, DimensionPercentiles { scores }))
+                .collect(),
+            dimensions: scores
+                .dimensions
+                .into_iter()
+                .map(|(dataset_uri, dimensions)| {
+                    let dimensions = dimensions.into_iter().map(Into::into).collect();
+                    (dataset_uri, DatasetDimensions { dimensions })
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<models::DatasetScore> for DatasetScore {
+    fn from(score: models::DatasetScore) -> Self {
+        DatasetScore {
+            dataset: Some((*score.dataset).into()),
+            distributions: score.distributions.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<models::Score> for Score {
+    fn from(score: models::Score) -> Self {
+        Score {
+            id: score.id,
+            dimensions: score.dimensions.into_iter().map(Into::into).collect(),
+            score: score.score,
+            max_score: score.max_score,
+            ratio: score.ratio,
+        }
+    }
+}
+
+impl From<models::Dimension> for Dimension {
+    fn from(dimension: models::Dimension) -> Self {
+        Dimension {
+            id: dimension.id,
+            metrics: dimension.metrics.into_iter().map(Into::into).collect(),
+            score: dimension.score,
+            max_score: dimension.max_score,
+            ratio: dimension.ratio,
+        }
+    }
+}
+
+impl From<models::Metric> for Metric {
+    fn from(metric: models::Metric) -> Self {
+        Metric {
+            id: metric.id,
+            score: metric.score,
+            is_scored: metric.is_scored,
+            max_score: metric.max_score,
+        }
+    }
+}
+
+impl From<models::AssessmentTimestamps> for AssessmentTimestamps {
+    fn from(timestamps: models::AssessmentTimestamps) -> Self {
+        AssessmentTimestamps {
+            created_at: timestamps.created_at,
+            updated_at: timestamps.updated_at,
+        }
+    }
+}
+
+impl From<models::DatasetDimension> for DatasetDimension {
+    fn from(dimension: models::DatasetDimension) -> Self {
+        DatasetDimension {
+            id: dimension.id,
+            score: dimension.score,
+            max_score: dimension.max_score,
+        }
+    }
+}
+
+impl From<models::DimensionAggregate> for DimensionAggregate {
+    fn from(aggregate: models::DimensionAggregate) -> Self {
+        DimensionAggregate {
+            id: aggregate.id,
+            score: aggregate.score,
+            max_score: aggregate.max_score,
+            min_observed_score: aggregate.min_observed_score,
+            max_observed_score: aggregate.max_observed_score,
+            min_ratio: aggregate.min_ratio,
+            max_ratio: aggregate.max_ratio,
+            ratio: aggregate.ratio,
+        }
+    }
+}
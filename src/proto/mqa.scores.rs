@@ -0,0 +1,116 @@
+// This file is @generated by prost-build.
+/// Mirrors the DatasetsScores schema of openapi.yaml.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DatasetsScores {
+    #[prost(map = "string, message", tag = "1")]
+    pub scores: ::std::collections::HashMap<::prost::alloc::string::String, DatasetScore>,
+    #[prost(message, repeated, tag = "2")]
+    pub aggregations: ::prost::alloc::vec::Vec<DimensionAggregate>,
+    #[prost(map = "string, message", tag = "3")]
+    pub timestamps: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        AssessmentTimestamps,
+    >,
+    /// Empty unless the request asks for percentiles.
+    #[prost(map = "string, message", tag = "4")]
+    pub percentiles: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        DimensionPercentiles,
+    >,
+    #[prost(map = "string, message", tag = "5")]
+    pub dimensions: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        DatasetDimensions,
+    >,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DatasetScore {
+    #[prost(message, optional, tag = "1")]
+    pub dataset: ::core::option::Option<Score>,
+    #[prost(message, repeated, tag = "2")]
+    pub distributions: ::prost::alloc::vec::Vec<Score>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Score {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "2")]
+    pub dimensions: ::prost::alloc::vec::Vec<Dimension>,
+    #[prost(int32, tag = "3")]
+    pub score: i32,
+    #[prost(int32, tag = "4")]
+    pub max_score: i32,
+    #[prost(double, tag = "5")]
+    pub ratio: f64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Dimension {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "2")]
+    pub metrics: ::prost::alloc::vec::Vec<Metric>,
+    #[prost(int32, tag = "3")]
+    pub score: i32,
+    #[prost(int32, tag = "4")]
+    pub max_score: i32,
+    #[prost(double, tag = "5")]
+    pub ratio: f64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Metric {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(int32, tag = "2")]
+    pub score: i32,
+    #[prost(bool, optional, tag = "3")]
+    pub is_scored: ::core::option::Option<bool>,
+    #[prost(int32, tag = "4")]
+    pub max_score: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AssessmentTimestamps {
+    #[prost(string, tag = "1")]
+    pub created_at: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub updated_at: ::prost::alloc::string::String,
+}
+/// Stored dimensions of a dataset, ordered by dimension id.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DatasetDimensions {
+    #[prost(message, repeated, tag = "1")]
+    pub dimensions: ::prost::alloc::vec::Vec<DatasetDimension>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DatasetDimension {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(int32, tag = "2")]
+    pub score: i32,
+    #[prost(int32, tag = "3")]
+    pub max_score: i32,
+}
+/// Score of a dimension at each requested percentile, keyed by the percentile.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DimensionPercentiles {
+    #[prost(map = "string, double", tag = "1")]
+    pub scores: ::std::collections::HashMap<::prost::alloc::string::String, f64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DimensionAggregate {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(double, tag = "2")]
+    pub score: f64,
+    #[prost(double, tag = "3")]
+    pub max_score: f64,
+    #[prost(double, optional, tag = "4")]
+    pub min_observed_score: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "5")]
+    pub max_observed_score: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "6")]
+    pub min_ratio: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "7")]
+    pub max_ratio: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "8")]
+    pub ratio: ::core::option::Option<f64>,
+}
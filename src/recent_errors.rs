@@ -0,0 +1,74 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use crate::models::RecentError;
+
+/// Errors kept when `RECENT_ERRORS_CAPACITY` is not set.
+pub const DEFAULT_CAPACITY: usize = 50;
+
+/// Last server errors shared by all workers, newest last. Only the request
+/// line, status and error message are kept; never bodies or headers.
+#[derive(Clone)]
+pub struct RecentErrors {
+    errors: Arc<Mutex<VecDeque<RecentError>>>,
+    capacity: usize,
+}
+
+impl Default for RecentErrors {
+    fn default() -> Self {
+        RecentErrors::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl RecentErrors {
+    pub fn new(capacity: usize) -> Self {
+        RecentErrors {
+            errors: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            capacity,
+        }
+    }
+
+    /// Records an error, evicting the oldest once the buffer is full.
+    pub fn push(&self, error: RecentError) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut errors = self.errors.lock().unwrap();
+        if errors.len() == self.capacity {
+            errors.pop_front();
+        }
+        errors.push_back(error);
+    }
+
+    /// The recorded errors, newest first.
+    pub fn list(&self) -> Vec<RecentError> {
+        self.errors.lock().unwrap().iter().rev().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(request_id: &str) -> RecentError {
+        RecentError {
+            request_id: request_id.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_push_evicts_oldest() {
+        let errors = RecentErrors::new(2);
+        for request_id in ["a", "b", "c"] {
+            errors.push(error(request_id));
+        }
+        assert_eq!(errors.list(), vec![error("c"), error("b")]);
+
+        let disabled = RecentErrors::new(0);
+        disabled.push(error("a"));
+        assert!(disabled.list().is_empty());
+    }
+}
@@ -0,0 +1,93 @@
+use std::str::FromStr;
+
+use actix_web::http::header::{HeaderMap, HeaderName, AUTHORIZATION};
+
+/// Replacement logged for the value of a redacted header.
+const REDACTED: &str = "***";
+
+/// Headers whose values are never logged. `X-API-KEY` and `Authorization` are
+/// always included; `REDACT_HEADERS` adds comma-separated names to them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RedactedHeaders(Vec<HeaderName>);
+
+impl Default for RedactedHeaders {
+    fn default() -> Self {
+        RedactedHeaders(vec![HeaderName::from_static("x-api-key"), AUTHORIZATION])
+    }
+}
+
+impl FromStr for RedactedHeaders {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut redacted = RedactedHeaders::default();
+        for name in s.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+            let name = HeaderName::from_str(name)
+                .map_err(|_| format!("expected comma-separated header names, got '{name}'"))?;
+            if !redacted.0.contains(&name) {
+                redacted.0.push(name);
+            }
+        }
+        Ok(redacted)
+    }
+}
+
+impl RedactedHeaders {
+    /// Formats the headers as `name: value` pairs sorted by name, with the
+    /// values of redacted headers replaced by `***`.
+    pub fn format(&self, headers: &HeaderMap) -> String {
+        let mut pairs: Vec<String> = headers
+            .iter()
+            .map(|(name, value)| {
+                let value = if self.0.contains(name) {
+                    REDACTED
+                } else {
+                    value.to_str().unwrap_or("<binary>")
+                };
+                format!("{name}: {value}")
+            })
+            .collect();
+        pairs.sort();
+        pairs.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::header::HeaderValue;
+
+    use super::*;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.append(
+                HeaderName::from_static(name),
+                HeaderValue::from_static(value),
+            );
+        }
+        headers
+    }
+
+    #[test]
+    fn test_format_redacts_defaults() {
+        let headers = headers(&[
+            ("x-api-key", "foo"),
+            ("authorization", "Bearer token"),
+            ("accept", "application/json"),
+        ]);
+        assert_eq!(
+            RedactedHeaders::default().format(&headers),
+            "accept: application/json, authorization: ***, x-api-key: ***"
+        );
+    }
+
+    #[test]
+    fn test_from_str_extends_defaults() {
+        let redacted: RedactedHeaders = " Cookie, x-api-key ,".parse().unwrap();
+        let headers = headers(&[("cookie", "session"), ("x-api-key", "foo")]);
+        assert_eq!(redacted.format(&headers), "cookie: ***, x-api-key: ***");
+
+        assert!("bad header".parse::<RedactedHeaders>().is_err());
+    }
+}
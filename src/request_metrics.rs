@@ -0,0 +1,174 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use crate::gauges::escape;
+
+/// Upper bounds in seconds of the duration histogram buckets.
+const BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Histogram {
+    /// Observations per bucket, not cumulative; the last counts those above
+    /// every bound.
+    counts: [u64; BUCKETS.len() + 1],
+    sum: f64,
+}
+
+impl Histogram {
+    fn observe(&mut self, duration: Duration) {
+        let seconds = duration.as_secs_f64();
+        let bucket = BUCKETS
+            .iter()
+            .position(|bound| seconds <= *bound)
+            .unwrap_or(BUCKETS.len());
+        self.counts[bucket] += 1;
+        self.sum += seconds;
+    }
+
+    /// Writes the `_bucket`, `_sum` and `_count` series of `name`, with
+    /// `labels` prepended to the labels of every series.
+    fn render(&self, text: &mut String, name: &str, labels: &str) {
+        let separator = if labels.is_empty() { "" } else { "," };
+        let mut cumulative = 0;
+        for (bound, count) in BUCKETS.iter().zip(self.counts) {
+            cumulative += count;
+            let _ = writeln!(
+                text,
+                "{name}_bucket{{{labels}{separator}le=\"{bound}\"}} {cumulative}"
+            );
+        }
+        let total: u64 = self.counts.iter().sum();
+        let _ = writeln!(
+            text,
+            "{name}_bucket{{{labels}{separator}le=\"+Inf\"}} {total}"
+        );
+        let labels = if labels.is_empty() {
+            String::new()
+        } else {
+            format!("{{{labels}}}")
+        };
+        let _ = writeln!(text, "{name}_sum{labels} {}", self.sum);
+        let _ = writeln!(text, "{name}_count{labels} {total}");
+    }
+}
+
+#[derive(Default)]
+struct Recorded {
+    /// Responses by method, route and status.
+    requests: BTreeMap<(String, String, u16), u64>,
+    /// Request durations by method and route.
+    durations: BTreeMap<(String, String), Histogram>,
+    queries: Histogram,
+}
+
+/// Request counts and durations, and database query durations, shared by all
+/// workers and rendered as Prometheus counters and histograms on `/metrics`.
+/// Requests are labelled with their route pattern, such as
+/// `/api/assessments/{id}`, so the number of series stays bounded.
+#[derive(Clone, Default)]
+pub struct RequestMetrics(Arc<Mutex<Recorded>>);
+
+impl RequestMetrics {
+    /// Records a response to a request, and the queries issued for it.
+    pub fn record(
+        &self,
+        method: &str,
+        route: &str,
+        status: u16,
+        duration: Duration,
+        queries: &[Duration],
+    ) {
+        let mut recorded = self.0.lock().unwrap();
+        *recorded
+            .requests
+            .entry((method.to_string(), route.to_string(), status))
+            .or_default() += 1;
+        recorded
+            .durations
+            .entry((method.to_string(), route.to_string()))
+            .or_default()
+            .observe(duration);
+        for query in queries {
+            recorded.queries.observe(*query);
+        }
+    }
+
+    /// The metrics in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let recorded = self.0.lock().unwrap();
+        let mut text = String::new();
+
+        text.push_str(
+            "# HELP mqa_http_requests_total Requests answered, by method, route and status.\n",
+        );
+        text.push_str("# TYPE mqa_http_requests_total counter\n");
+        for ((method, route, status), count) in &recorded.requests {
+            let _ = writeln!(
+                text,
+                "mqa_http_requests_total{{method=\"{}\",route=\"{}\",status=\"{status}\"}} {count}",
+                escape(method),
+                escape(route),
+            );
+        }
+        text.push_str("# HELP mqa_http_request_duration_seconds Time taken to answer requests, by method and route.\n");
+        text.push_str("# TYPE mqa_http_request_duration_seconds histogram\n");
+        for ((method, route), histogram) in &recorded.durations {
+            histogram.render(
+                &mut text,
+                "mqa_http_request_duration_seconds",
+                &format!("method=\"{}\",route=\"{}\"", escape(method), escape(route)),
+            );
+        }
+        text.push_str("# HELP mqa_db_query_duration_seconds Time taken by database queries.\n");
+        text.push_str("# TYPE mqa_db_query_duration_seconds histogram\n");
+        recorded
+            .queries
+            .render(&mut text, "mqa_db_query_duration_seconds", "");
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let metrics = RequestMetrics::default();
+        let millis = Duration::from_millis;
+        metrics.record(
+            "GET",
+            "/api/assessments/{id}",
+            200,
+            millis(20),
+            &[millis(1), millis(30)],
+        );
+        metrics.record("GET", "/api/assessments/{id}", 404, millis(3), &[]);
+
+        let text = metrics.render();
+        assert!(text.contains(
+            "mqa_http_requests_total{method=\"GET\",route=\"/api/assessments/{id}\",status=\"200\"} 1\n"
+        ));
+        assert!(text.contains(
+            "mqa_http_requests_total{method=\"GET\",route=\"/api/assessments/{id}\",status=\"404\"} 1\n"
+        ));
+        assert!(text.contains(
+            "mqa_http_request_duration_seconds_bucket{method=\"GET\",route=\"/api/assessments/{id}\",le=\"0.005\"} 1\n"
+        ));
+        assert!(text.contains(
+            "mqa_http_request_duration_seconds_bucket{method=\"GET\",route=\"/api/assessments/{id}\",le=\"0.025\"} 2\n"
+        ));
+        assert!(text.contains(
+            "mqa_http_request_duration_seconds_count{method=\"GET\",route=\"/api/assessments/{id}\"} 2\n"
+        ));
+        assert!(text.contains("mqa_db_query_duration_seconds_bucket{le=\"0.01\"} 1\n"));
+        assert!(text.contains("mqa_db_query_duration_seconds_bucket{le=\"+Inf\"} 2\n"));
+        assert!(text.contains("mqa_db_query_duration_seconds_sum 0.031\n"));
+    }
+}
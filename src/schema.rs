@@ -1,10 +1,53 @@
 table! {
-    dataset_assessments (id) {
+    aggregate_cache (scope, dimension_id) {
+        scope -> Varchar,
+        dimension_id -> Varchar,
+        score -> Float8,
+        max_score -> Float8,
+        computed_at -> Timestamptz,
+    }
+}
+
+table! {
+    annotations (assessment_id, key) {
+        assessment_id -> Varchar,
+        key -> Varchar,
+        value -> Varchar,
+    }
+}
+
+table! {
+    assessment_graphs (id) {
         id -> Varchar,
-        dataset_uri -> Varchar,
         turtle_assessment -> Varchar,
         jsonld_assessment -> Varchar,
+    }
+}
+
+table! {
+    assessment_history (id) {
+        id -> Int8,
+        assessment_id -> Varchar,
+        recorded_at -> Timestamptz,
+        turtle_assessment -> Varchar,
+        jsonld_assessment -> Varchar,
+        json_score -> Varchar,
+    }
+}
+
+table! {
+    dataset_assessments (id) {
+        id -> Varchar,
+        dataset_uri -> Varchar,
+        turtle_assessment -> Nullable<Varchar>,
+        jsonld_assessment -> Nullable<Varchar>,
         json_score -> Varchar,
+        updated_at -> Timestamptz,
+        catalog_uri -> Nullable<Varchar>,
+        publisher_uri -> Nullable<Varchar>,
+        content_hash -> Nullable<Varchar>,
+        created_at -> Timestamptz,
+        distribution_count -> Int4,
     }
 }
 
@@ -17,4 +60,13 @@ table! {
     }
 }
 
-allow_tables_to_appear_in_same_query!(dataset_assessments, dimensions,);
+joinable!(assessment_graphs -> dataset_assessments (id));
+
+allow_tables_to_appear_in_same_query!(
+    aggregate_cache,
+    annotations,
+    assessment_graphs,
+    assessment_history,
+    dataset_assessments,
+    dimensions,
+);
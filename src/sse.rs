@@ -0,0 +1,56 @@
+//! Server-Sent Events written on a blocking thread and streamed to the client
+//! as they are produced.
+
+use std::{
+    convert::Infallible,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use actix_web::{
+    body::{BodySize, MessageBody},
+    web::Bytes,
+};
+use serde::Serialize;
+use tokio::sync::mpsc;
+
+/// Events buffered ahead of a slow client before the writer blocks.
+const BUFFERED_EVENTS: usize = 16;
+
+/// Writing half of an event stream.
+pub struct EventWriter(mpsc::Sender<Bytes>);
+
+/// Response body yielding the events as the writer sends them.
+pub struct EventBody(mpsc::Receiver<Bytes>);
+
+pub fn channel() -> (EventWriter, EventBody) {
+    let (sender, receiver) = mpsc::channel(BUFFERED_EVENTS);
+    (EventWriter(sender), EventBody(receiver))
+}
+
+impl EventWriter {
+    /// Sends `data` as JSON in an event named `event`, blocking while the
+    /// client is behind. Returns `false` once the client has gone away.
+    pub fn send(&self, event: &str, data: &impl Serialize) -> bool {
+        let data = serde_json::to_string(data)
+            .unwrap_or_else(|e| serde_json::json!({ "error": e.to_string() }).to_string());
+        self.0
+            .blocking_send(Bytes::from(format!("event: {event}\ndata: {data}\n\n")))
+            .is_ok()
+    }
+}
+
+impl MessageBody for EventBody {
+    type Error = Infallible;
+
+    fn size(&self) -> BodySize {
+        BodySize::Stream
+    }
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Bytes, Infallible>>> {
+        self.0.poll_recv(cx).map(|event| event.map(Ok))
+    }
+}
@@ -0,0 +1,47 @@
+//! IRIs of the vocabularies used in assessment graphs.
+
+/// Prefixes declared in the `@context` of JSON-LD documents combining several
+/// assessment graphs.
+pub const JSON_LD_PREFIXES: [(&str, &str); 5] = [
+    ("dcat", "http://www.w3.org/ns/dcat#"),
+    ("dct", "http://purl.org/dc/terms/"),
+    ("dqv", "http://www.w3.org/ns/dqv#"),
+    ("mqa", "https://data.norge.no/vocabulary/dcatno-mqa#"),
+    ("xsd", "http://www.w3.org/2001/XMLSchema#"),
+];
+
+pub mod dct {
+    use oxigraph::model::NamedNodeRef;
+
+    pub const PUBLISHER: NamedNodeRef<'_> =
+        NamedNodeRef::new_unchecked("http://purl.org/dc/terms/publisher");
+}
+
+pub mod dqv {
+    use oxigraph::model::NamedNodeRef;
+
+    pub const COMPUTED_ON: NamedNodeRef<'_> =
+        NamedNodeRef::new_unchecked("http://www.w3.org/ns/dqv#computedOn");
+    pub const IN_DIMENSION: NamedNodeRef<'_> =
+        NamedNodeRef::new_unchecked("http://www.w3.org/ns/dqv#inDimension");
+    pub const IS_MEASUREMENT_OF: NamedNodeRef<'_> =
+        NamedNodeRef::new_unchecked("http://www.w3.org/ns/dqv#isMeasurementOf");
+    pub const VALUE: NamedNodeRef<'_> =
+        NamedNodeRef::new_unchecked("http://www.w3.org/ns/dqv#value");
+}
+
+pub mod mqa {
+    /// The dimensions of the MQA vocabulary that datasets and distributions are
+    /// scored in.
+    pub const DIMENSIONS: [&str; 5] = [
+        "https://data.norge.no/vocabulary/dcatno-mqa#accessibility",
+        "https://data.norge.no/vocabulary/dcatno-mqa#contextuality",
+        "https://data.norge.no/vocabulary/dcatno-mqa#findability",
+        "https://data.norge.no/vocabulary/dcatno-mqa#interoperability",
+        "https://data.norge.no/vocabulary/dcatno-mqa#reusability",
+    ];
+
+    /// Suffix of the metric whose measurement holds a dimension's score, as in
+    /// `mqa:accessibilityScoring` for `mqa:accessibility`.
+    pub const DIMENSION_SCORING_SUFFIX: &str = "Scoring";
+}
@@ -0,0 +1,61 @@
+use std::sync::{Arc, RwLock};
+
+use chrono::{DateTime, Utc};
+
+use crate::error::Error;
+
+/// Scheduled write freeze shared by all workers. While the freeze lasts, write
+/// endpoints are answered with 503 and a `Retry-After` hint.
+#[derive(Clone, Default)]
+pub struct WriteFreeze(Arc<RwLock<Option<DateTime<Utc>>>>);
+
+impl WriteFreeze {
+    pub fn new(until: Option<DateTime<Utc>>) -> Self {
+        WriteFreeze(Arc::new(RwLock::new(until)))
+    }
+
+    pub fn until(&self) -> Option<DateTime<Utc>> {
+        *self.0.read().unwrap()
+    }
+
+    pub fn set(&self, until: Option<DateTime<Utc>>) {
+        *self.0.write().unwrap() = until;
+    }
+
+    /// Fails with the number of seconds left of the freeze, if one is active.
+    pub fn check(&self) -> Result<(), Error> {
+        match retry_after(self.until(), Utc::now()) {
+            Some(seconds) => Err(Error::WriteFrozen(seconds)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Whole seconds until `until`, rounded up, or `None` once it has passed.
+fn retry_after(until: Option<DateTime<Utc>>, now: DateTime<Utc>) -> Option<u64> {
+    let remaining = (until? - now).num_milliseconds();
+    (remaining > 0).then(|| (remaining as u64).div_ceil(1000))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+
+    #[test]
+    fn test_retry_after() {
+        let now = Utc::now();
+
+        assert_eq!(retry_after(None, now), None);
+        assert_eq!(retry_after(Some(now - TimeDelta::seconds(1)), now), None);
+        assert_eq!(retry_after(Some(now), now), None);
+        assert_eq!(
+            retry_after(Some(now + TimeDelta::milliseconds(1)), now),
+            Some(1)
+        );
+        assert_eq!(
+            retry_after(Some(now + TimeDelta::seconds(90)), now),
+            Some(90)
+        );
+    }
+}